@@ -1,3 +1,3 @@
 #!/bin/bash
 
-cargo run --bin day$1
+cargo run --bin aoc -- "$@"
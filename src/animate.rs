@@ -0,0 +1,252 @@
+use crate::days::y2021::{day11::Day11, day13::Day13, day17::Day17};
+use crate::error::AocError;
+use crate::geometry::Point2;
+use crate::solution::Solution;
+
+use std::{
+    collections::HashSet,
+    io::{self, BufRead, Write},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+pub const DEFAULT_FPS: f64 = 10.0;
+
+const CLEAR: &str = "\x1b[H\x1b[2J";
+const RESET: &str = "\x1b[0m";
+
+// One state of a simulation as terminal lines, which may hold colour codes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+// Days whose solution goes through states worth watching
+pub trait Animate: Solution {
+    fn frames(input: &Self::Input) -> Vec<Frame>;
+}
+
+// The object safe view of Animate, for the registry below
+pub trait Animation: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn animate(&self, input: Vec<String>) -> Result<Vec<Frame>, AocError>;
+}
+
+impl<A: Animate + Sync> Animation for A {
+    fn year(&self) -> u32 {
+        A::YEAR
+    }
+
+    fn day(&self) -> u32 {
+        A::DAY
+    }
+
+    fn animate(&self, input: Vec<String>) -> Result<Vec<Frame>, AocError> {
+        Ok(A::frames(&A::parse(input)?))
+    }
+}
+
+static ANIMATIONS: [&dyn Animation; 3] = [&Day11, &Day13, &Day17];
+
+pub fn get(year: u32, day: u32) -> Option<&'static dyn Animation> {
+    ANIMATIONS
+        .iter()
+        .copied()
+        .find(|a| a.year() == year && a.day() == day)
+}
+
+// Text on a background from the 256 colour palette
+pub fn on(background: u8, text: &str) -> String {
+    format!("\x1b[48;5;{}m{}{}", background, text, RESET)
+}
+
+// Text in a colour from the 256 colour palette
+pub fn colored(foreground: u8, text: &str) -> String {
+    format!("\x1b[38;5;{}m{}{}", foreground, text, RESET)
+}
+
+// A value in 0..=max as a palette colour going from dark blue to bright cyan
+pub fn ramp(value: u64, max: u64) -> u8 {
+    const COLORS: [u8; 10] = [16, 17, 18, 19, 20, 21, 27, 33, 39, 51];
+    let index = value.min(max) * (COLORS.len() as u64 - 1) / max.max(1);
+    COLORS[index as usize]
+}
+
+// Points shrunk to fit in `width` x `height` characters, each one showing whether any point falls
+// in the block of the plane it stands for
+pub fn downscale(points: &HashSet<Point2>, width: usize, height: usize) -> Vec<String> {
+    let cols = points.iter().map(|p| p.x + 1).max().unwrap_or(1) as usize;
+    let rows = points.iter().map(|p| p.y + 1).max().unwrap_or(1) as usize;
+    let (block_x, block_y) = (cols.div_ceil(width), rows.div_ceil(height));
+
+    let mut lines = vec![vec![' '; cols.div_ceil(block_x)]; rows.div_ceil(block_y)];
+    for p in points.iter().filter(|p| p.x >= 0 && p.y >= 0) {
+        lines[p.y as usize / block_y][p.x as usize / block_x] = '#';
+    }
+    lines.into_iter().map(|l| l.into_iter().collect()).collect()
+}
+
+// What can be typed while an animation plays, each followed by enter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    // an empty line
+    TogglePause,
+    // `n`, shows the next frame and pauses
+    Step,
+    // `q`
+    Quit,
+}
+
+impl Control {
+    fn from_line(line: &str) -> Option<Control> {
+        match line.trim() {
+            "" | "p" => Some(Control::TogglePause),
+            "n" => Some(Control::Step),
+            "q" => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+// Reads controls from stdin on a thread of its own, so frames keep coming while nothing is typed
+pub fn stdin_controls() -> Receiver<Control> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if let Some(control) = Control::from_line(&line) {
+                if sender.send(control).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+// Shows the frames one after the other at `fps`, redrawing the terminal each time. Returns how many
+// frames were shown, which is less than all of them when quitting
+pub fn play(
+    frames: &[Frame],
+    fps: f64,
+    controls: &Receiver<Control>,
+    out: &mut impl Write,
+) -> io::Result<usize> {
+    let delay = Duration::from_secs_f64(1.0 / fps);
+    let mut paused = false;
+    let mut shown = 0;
+
+    while shown < frames.len() {
+        draw(&frames[shown], shown, frames.len(), paused, out)?;
+        shown += 1;
+        if shown == frames.len() {
+            break;
+        }
+
+        // while paused nothing happens until something is typed
+        let control = if paused {
+            controls.recv().ok()
+        } else {
+            match controls.recv_timeout(delay) {
+                Ok(control) => Some(control),
+                Err(RecvTimeoutError::Timeout) => None,
+                // nothing will ever be typed, play the rest
+                Err(RecvTimeoutError::Disconnected) => {
+                    thread::sleep(delay);
+                    None
+                }
+            }
+        };
+        match control {
+            Some(Control::Quit) => break,
+            Some(Control::TogglePause) => paused = !paused,
+            Some(Control::Step) => paused = true,
+            None if paused => paused = false,
+            None => {}
+        }
+    }
+
+    out.flush()?;
+    Ok(shown)
+}
+
+fn draw(
+    frame: &Frame,
+    index: usize,
+    total: usize,
+    paused: bool,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut screen = format!("{}{}\n", CLEAR, frame.title);
+    for line in &frame.lines {
+        screen.push_str(line);
+        screen.push('\n');
+    }
+    screen.push_str(&format!(
+        "frame {}/{}{}  (enter: pause/resume, n: next frame, q: quit)\n",
+        index + 1,
+        total,
+        if paused { ", paused" } else { "" }
+    ));
+    out.write_all(screen.as_bytes())?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn frames(count: usize) -> Vec<Frame> {
+        (0..count)
+            .map(|i| Frame {
+                title: format!("frame {}", i),
+                lines: vec!["#".repeat(i)],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plays_until_quit() {
+        let (sender, controls) = mpsc::channel();
+        // pausing waits for the next control, stepping shows a frame at a time, then quit
+        for control in [Control::TogglePause, Control::Step, Control::Quit] {
+            sender.send(control).unwrap();
+        }
+        let mut out = Vec::new();
+        let shown = play(&frames(10), 1000.0, &controls, &mut out).unwrap();
+        assert_eq!(shown, 3);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\x1b[H\x1b[2Jframe 0\n\nframe 1/10  (enter"));
+        assert!(out.contains("frame 3/10, paused"));
+
+        drop(sender);
+        let mut out = Vec::new();
+        assert_eq!(play(&frames(4), 1000.0, &controls, &mut out).unwrap(), 4);
+    }
+
+    #[test]
+    fn scales_points_and_values() {
+        let points = [(0, 0), (5, 0), (9, 3)]
+            .iter()
+            .map(|&(x, y)| Point2::new(x, y))
+            .collect::<HashSet<_>>();
+        assert_eq!(downscale(&points, 5, 2), vec!["# #  ", "    #"]);
+        assert_eq!((ramp(0, 9), ramp(9, 9), ramp(20, 9)), (16, 51, 51));
+    }
+
+    #[test]
+    fn animates_samples() {
+        let root = resources_root(None);
+        for animation in ANIMATIONS.iter() {
+            let sample = sample_data(&root, animation.year(), animation.day()).unwrap();
+            let frames = animation.animate(sample).unwrap();
+            assert!(frames.len() > 1);
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use crate::error::AocError;
+use crate::helpers::*;
+use crate::memory::{self, Usage};
+use crate::numeric::Policy;
+use crate::solution::{Part, Puzzle};
+
+use std::{collections::HashMap, fmt::Display, fs, path::Path, time::Duration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    fn from_name(name: &str) -> Option<Phase> {
+        Phase::ALL.into_iter().find(|p| p.to_string() == name)
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part1"),
+            Phase::Part2 => write!(f, "part2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayBench {
+    pub year: u32,
+    pub day: u32,
+    pub phases: Vec<(Phase, Stats)>,
+    // what each phase allocated on the last run, when allocations are being counted
+    pub memory: HashMap<Phase, Usage>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchOptions {
+    pub iterations: usize,
+    // compare the medians against this file
+    pub baseline: Option<String>,
+    // store the medians of this run in this file
+    pub save_baseline: Option<String>,
+    // how much slower (in percent) than the baseline a phase can be before it counts as a regression
+    pub tolerance: f64,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            iterations: 5,
+            baseline: None,
+            save_baseline: None,
+            tolerance: 20.0,
+        }
+    }
+}
+
+// runs the day `iterations` times, timing parse and each part separately
+pub fn bench(
+    puzzle: &dyn Puzzle,
+    input: &[String],
+    iterations: usize,
+    overflow: Policy,
+) -> Result<DayBench, AocError> {
+    let mut samples: HashMap<Phase, Vec<Duration>> = HashMap::new();
+    let mut memory = HashMap::new();
+    for _ in 0..iterations.max(1) {
+        let execution = puzzle.execute(input.to_vec(), &Part::ALL, overflow)?;
+        samples
+            .entry(Phase::Parse)
+            .or_default()
+            .push(execution.parse_time);
+        if let Some(usage) = execution.parse_memory {
+            memory.insert(Phase::Parse, usage);
+        }
+        for result in execution.results {
+            let phase = match result.part {
+                Part::One => Phase::Part1,
+                Part::Two => Phase::Part2,
+            };
+            samples.entry(phase).or_default().push(result.time);
+            if let Some(usage) = result.memory {
+                memory.insert(phase, usage);
+            }
+        }
+    }
+
+    let phases = Phase::ALL
+        .into_iter()
+        .filter_map(|phase| {
+            samples
+                .remove(&phase)
+                .map(|s| (phase, Stats::from_samples(s)))
+        })
+        .collect();
+    Ok(DayBench {
+        year: puzzle.year(),
+        day: puzzle.day(),
+        phases,
+        memory,
+    })
+}
+
+// Median time per day and phase from a previous run, stored as `<year> <day> <phase> <nanoseconds>` lines
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Baseline {
+    medians: HashMap<(u32, u32, Phase), Duration>,
+}
+
+impl Baseline {
+    pub fn from_results(results: &[DayBench]) -> Baseline {
+        let medians = results
+            .iter()
+            .flat_map(|r| {
+                r.phases
+                    .iter()
+                    .map(|(p, s)| ((r.year, r.day, *p), s.median))
+            })
+            .collect();
+        Baseline { medians }
+    }
+
+    pub fn load(path: &Path) -> Result<Baseline, AocError> {
+        Baseline::parse(read_file(path)?).map_err(|e| e.in_file(&path.display().to_string()))
+    }
+
+    pub fn parse(input: Vec<String>) -> Result<Baseline, AocError> {
+        let mut medians = HashMap::new();
+        for (i, line) in input.iter().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            if fields.len() != 4 {
+                return Err(line_error(
+                    line,
+                    i,
+                    "expected '<year> <day> <phase> <nanoseconds>'",
+                ));
+            }
+            let year = parse_value::<u32>(line, i, fields[0])?;
+            let day = parse_value::<u32>(line, i, fields[1])?;
+            let phase = Phase::from_name(fields[2]).ok_or_else(|| {
+                AocError::parse(
+                    i + 1,
+                    column_of(line, fields[2]),
+                    fields[2],
+                    "phase must be parse, part1 or part2",
+                )
+            })?;
+            let nanos = parse_value::<u64>(line, i, fields[3])?;
+            medians.insert((year, day, phase), Duration::from_nanos(nanos));
+        }
+        Ok(Baseline { medians })
+    }
+
+    // Stores the medians of `results` in the file, keeping the ones of other days and years already there
+    pub fn update(path: &Path, results: &[DayBench]) -> Result<(), AocError> {
+        let mut baseline = match Baseline::load(path) {
+            Ok(baseline) => baseline,
+            Err(AocError::MissingFile { .. }) => Baseline::default(),
+            Err(e) => return Err(e),
+        };
+        baseline
+            .medians
+            .extend(Baseline::from_results(results).medians);
+        baseline.save(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AocError> {
+        let mut entries = self.medians.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|((year, day, phase), _)| {
+            (*year, *day, Phase::ALL.iter().position(|p| p == phase))
+        });
+
+        let mut text =
+            String::from("# median time per phase: <year> <day> <phase> <nanoseconds>\n");
+        for ((year, day, phase), median) in entries {
+            text.push_str(&format!(
+                "{} {} {} {}\n",
+                year,
+                day,
+                phase,
+                median.as_nanos()
+            ));
+        }
+        fs::write(path, text).map_err(|source| AocError::Io {
+            path: path.display().to_string(),
+            source,
+        })
+    }
+
+    pub fn get(&self, year: u32, day: u32, phase: Phase) -> Option<Duration> {
+        self.medians.get(&(year, day, phase)).copied()
+    }
+}
+
+// change of the median against the baseline, in percent (positive means slower)
+pub fn change(median: Duration, baseline: Duration) -> f64 {
+    let base = baseline.as_secs_f64();
+    if base == 0.0 {
+        0.0
+    } else {
+        (median.as_secs_f64() - base) / base * 100.0
+    }
+}
+
+// prints the timings and returns how many phases are slower than the baseline allows
+pub fn report(results: &[DayBench], baseline: Option<&Baseline>, tolerance: f64) -> usize {
+    let mut regressions = 0;
+    let counted = results.iter().any(|r| !r.memory.is_empty());
+    let memory_columns = |allocations: &str, allocated: &str, peak: &str| {
+        if counted {
+            format!("  {:>12}  {:>10}  {:>10}", allocations, allocated, peak)
+        } else {
+            String::new()
+        }
+    };
+    println!(
+        "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}  {:>10}{}",
+        "day",
+        "phase",
+        "min",
+        "median",
+        "max",
+        "baseline",
+        memory_columns("allocations", "allocated", "peak")
+    );
+    for result in results {
+        for (phase, stats) in &result.phases {
+            let comparison = match baseline.and_then(|b| b.get(result.year, result.day, *phase)) {
+                Some(base) => {
+                    let delta = change(stats.median, base);
+                    let regressed = delta > tolerance;
+                    if regressed {
+                        regressions += 1;
+                    }
+                    format!(
+                        "{:+.1}%{}",
+                        delta,
+                        if regressed { " REGRESSION" } else { "" }
+                    )
+                }
+                None => "-".to_string(),
+            };
+            let usage = result.memory.get(phase).copied().unwrap_or_default();
+            println!(
+                "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}  {:>10}{}",
+                result.day,
+                phase.to_string(),
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.max),
+                comparison,
+                memory_columns(
+                    &usage.allocations.to_string(),
+                    &memory::format_bytes(usage.bytes),
+                    &memory::format_bytes(usage.peak)
+                )
+            );
+        }
+    }
+    regressions
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.3}s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_use_middle_samples() {
+        let samples = [5, 1, 3, 2]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn baseline_round_trips() {
+        let day = |year, median| DayBench {
+            year,
+            day: 15,
+            phases: vec![(
+                Phase::Part2,
+                Stats {
+                    min: Duration::from_millis(1),
+                    median: Duration::from_millis(median),
+                    max: Duration::from_millis(3),
+                },
+            )],
+            memory: HashMap::new(),
+        };
+        let baseline = Baseline::from_results(&[day(2021, 2), day(2022, 1)]);
+        // the same day of two years is kept apart
+        assert_eq!(
+            baseline.get(2021, 15, Phase::Part2),
+            Some(Duration::from_millis(2))
+        );
+        assert_eq!(
+            baseline.get(2022, 15, Phase::Part2),
+            Some(Duration::from_millis(1))
+        );
+        let path = std::env::temp_dir().join(format!(
+            "aoc-baseline-{}-baseline_round_trips.txt",
+            std::process::id()
+        ));
+        baseline.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+
+        Baseline::update(&path, &[day(2022, 4)]).unwrap();
+        let updated = Baseline::load(&path).unwrap();
+        assert_eq!(
+            updated.get(2021, 15, Phase::Part2),
+            Some(Duration::from_millis(2))
+        );
+        assert_eq!(
+            updated.get(2022, 15, Phase::Part2),
+            Some(Duration::from_millis(4))
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn change_is_relative_to_baseline() {
+        let delta = change(Duration::from_millis(150), Duration::from_millis(100));
+        assert!((delta - 50.0).abs() < 1e-9);
+    }
+}
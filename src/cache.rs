@@ -0,0 +1,181 @@
+use crate::error::AocError;
+use crate::helpers::*;
+use crate::solution::Part;
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+// 64 bit FNV-1a, small and good enough to tell inputs apart
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+// the hash of the input as it is in the file, lines ending in a line break
+pub fn input_hash(input: &[String]) -> u64 {
+    let mut text = input.join("\n");
+    text.push('\n');
+    fnv1a(text.as_bytes())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    version: String,
+    hash: u64,
+    answer: String,
+}
+
+// Answers already found for a year, kept in `cache.txt` in its resources folder as
+// `<day> <part> <solver version> <input hash> <answer>` lines. Only the last answer of each part is
+// kept, so a new input or solver version replaces it
+#[derive(Debug)]
+pub struct Cache {
+    path: PathBuf,
+    entries: Mutex<HashMap<(u32, Part), Entry>>,
+    changed: Mutex<bool>,
+}
+
+impl Cache {
+    pub fn load(path: &Path) -> Result<Cache, AocError> {
+        let entries = match read_file(path) {
+            Ok(lines) => Cache::parse(lines).map_err(|e| e.in_file(&path.display().to_string()))?,
+            Err(AocError::MissingFile { .. }) => HashMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Cache {
+            path: path.to_path_buf(),
+            entries: Mutex::new(entries),
+            changed: Mutex::new(false),
+        })
+    }
+
+    fn parse(input: Vec<String>) -> Result<HashMap<(u32, Part), Entry>, AocError> {
+        let mut entries = HashMap::new();
+        for (i, line) in input.iter().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.splitn(5, ' ').collect::<Vec<_>>();
+            if fields.len() != 5 {
+                return Err(line_error(
+                    line,
+                    i,
+                    "expected '<day> <part> <version> <hash> <answer>'",
+                ));
+            }
+            let part = parse_value::<u32>(line, i, fields[1])?;
+            let part = Part::from_number(part).ok_or_else(|| {
+                AocError::parse(
+                    i + 1,
+                    column_of(line, fields[1]),
+                    fields[1],
+                    "part must be 1 or 2",
+                )
+            })?;
+            let hash = u64::from_str_radix(fields[3], 16).map_err(|e| {
+                AocError::parse(i + 1, column_of(line, fields[3]), fields[3], e.to_string())
+            })?;
+            let entry = Entry {
+                version: fields[2].to_string(),
+                hash,
+                answer: unescape(fields[4]),
+            };
+            entries.insert((parse_value(line, i, fields[0])?, part), entry);
+        }
+        Ok(entries)
+    }
+
+    // the answer found before for the part, if it was for the same input and solver version
+    pub fn get(&self, day: u32, part: Part, version: &str, hash: u64) -> Option<String> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(&(day, part))
+            .filter(|e| e.version == version && e.hash == hash)
+            .map(|e| e.answer.clone())
+    }
+
+    pub fn put(&self, day: u32, part: Part, version: &str, hash: u64, answer: &str) {
+        let entry = Entry {
+            version: version.to_string(),
+            hash,
+            answer: answer.to_string(),
+        };
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.insert((day, part), entry.clone()) != Some(entry) {
+            *self.changed.lock().unwrap_or_else(|e| e.into_inner()) = true;
+        }
+    }
+
+    // writes the file again if any answer was added
+    pub fn save(&self) -> Result<(), AocError> {
+        if !*self.changed.lock().unwrap_or_else(|e| e.into_inner()) {
+            return Ok(());
+        }
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut sorted = entries.iter().collect::<Vec<_>>();
+        sorted.sort_by_key(|((day, part), _)| (*day, *part == Part::Two));
+
+        let mut text = String::from("# <day> <part> <solver version> <input hash> <answer>\n");
+        for ((day, part), entry) in sorted {
+            text.push_str(&format!(
+                "{} {} {} {:016x} {}\n",
+                day,
+                part,
+                entry.version,
+                entry.hash,
+                escape(&entry.answer)
+            ));
+        }
+        fs::write(&self.path, text).map_err(|source| AocError::Io {
+            path: self.path.display().to_string(),
+            source,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_like_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(
+            input_hash(&["1".to_string(), "2".to_string()]),
+            input_hash(&["12".to_string()])
+        );
+    }
+
+    #[test]
+    fn answers_only_for_same_input_and_version() {
+        let path = std::env::temp_dir().join(format!(
+            "aoc-cache-{}-answers_only_for_same_input_and_version.txt",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let cache = Cache::load(&path).unwrap();
+        cache.put(15, Part::Two, "1", 42, "2846");
+        cache.put(13, Part::Two, "1", 7, "#.\n.#");
+        cache.put(14, Part::One, "1", 9, "a\\nb\\");
+        cache.save().unwrap();
+
+        let cache = Cache::load(&path).unwrap();
+        assert_eq!(cache.get(15, Part::Two, "1", 42), Some("2846".to_string()));
+        assert_eq!(cache.get(13, Part::Two, "1", 7), Some("#.\n.#".to_string()));
+        assert_eq!(
+            cache.get(14, Part::One, "1", 9),
+            Some("a\\nb\\".to_string())
+        );
+        assert_eq!(cache.get(15, Part::Two, "2", 42), None);
+        assert_eq!(cache.get(15, Part::Two, "1", 43), None);
+        assert_eq!(cache.get(15, Part::One, "1", 42), None);
+        fs::remove_file(path).unwrap();
+    }
+}
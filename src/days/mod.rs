@@ -0,0 +1,22 @@
+use crate::solution::Puzzle;
+
+pub mod y2021;
+
+// Every year with solutions, each one keeping the registry of its own days in its module
+static YEARS: [(u32, &[&dyn Puzzle]); 1] = [(2021, &y2021::PUZZLES)];
+
+pub fn years() -> impl Iterator<Item = u32> {
+    YEARS.iter().map(|&(year, _)| year)
+}
+
+// the days of a year, none for a year without solutions
+pub fn all(year: u32) -> &'static [&'static dyn Puzzle] {
+    YEARS
+        .iter()
+        .find(|&&(y, _)| y == year)
+        .map_or(&[], |&(_, days)| days)
+}
+
+pub fn get(year: u32, day: u32) -> Option<&'static dyn Puzzle> {
+    all(year).iter().find(|p| p.day() == day).copied()
+}
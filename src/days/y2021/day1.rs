@@ -0,0 +1,82 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::parser::lines;
+use crate::property::{int, shrink_int, shrink_vec, vec_of};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 1;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (7, 5)
+    }
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Vec<i32>, AocError> {
+    lines(&input).map(|line| line.value()).collect()
+}
+
+impl RoundTrip for Day1 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        input.iter().map(|depth| depth.to_string()).collect()
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        vec_of(rng, 0, size, |rng| int(rng, i32::MIN, i32::MAX))
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_vec(input, 0, |&depth| shrink_int(depth))
+    }
+}
+
+pub fn part_1(input: Vec<i32>) -> usize {
+    let windows = input.windows(2);
+    windows.filter(|w| w[0] < w[1]).count()
+}
+
+pub fn part_2(input: Vec<i32>) -> usize {
+    let windows = input.windows(3).map(|w| w.iter().sum()).collect::<Vec<i32>>();
+    windows.windows(2).filter(|w| w[0] < w[1]).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day1 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 1).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 7);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 5);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::helpers::first_line;
+use crate::property::{shrink_vec, vec_of, word};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Vec<char>>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 10;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (26397, 288957)
+    }
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Vec<Vec<char>>, AocError> {
+    first_line(&input)?;
+    input
+        .into_iter()
+        .enumerate()
+        .map(|(i, s)| {
+            if let Some((c, _)) = s.char_indices().find(|&(_, ch)| !"()[]{}<>".contains(ch)) {
+                return Err(AocError::parse(i + 1, c + 1, &s, "expected a bracket"));
+            }
+            Ok(s.chars().collect::<Vec<_>>())
+        })
+        .collect()
+}
+
+impl RoundTrip for Day10 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        input.iter().map(|line| line.iter().collect()).collect()
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        vec_of(rng, 1, size, |rng| {
+            word(rng, "()[]{}<>", 0, size * 4).chars().collect()
+        })
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_vec(input, 1, |line| shrink_vec(line, 0, |_| Vec::new()))
+    }
+}
+
+pub fn part_1(input: Vec<Vec<char>>) -> u64 {
+    trace!("input: {:?}", input);
+    input
+        .into_iter()
+        .map(|row| match find_illegal(row) {
+            Some(ic) => score_illegal(ic),
+            None => 0,
+        })
+        .sum()
+}
+
+fn find_illegal(row: Vec<char>) -> Option<char> {
+    let mut stack: Vec<char> = Vec::new();
+    let mut answer: Option<char> = None;
+    for c in row {
+        match c {
+            '(' | '[' | '{' | '<' => stack.push(c),
+            ']' if stack.pop().unwrap() != '[' => {
+                answer = Some(c);
+            }
+            ')' if stack.pop().unwrap() != '(' => {
+                answer = Some(c);
+            }
+            '}' if stack.pop().unwrap() != '{' => {
+                answer = Some(c);
+            }
+            '>' if stack.pop().unwrap() != '<' => {
+                answer = Some(c);
+            }
+            _ => {}
+        }
+    }
+    answer
+}
+
+fn score_illegal(c: char) -> u64 {
+    match c {
+        ')' => 3,
+        ']' => 57,
+        '}' => 1197,
+        '>' => 25137,
+        _ => 0,
+    }
+}
+
+pub fn part_2(input: Vec<Vec<char>>) -> u64 {
+    let mut scores = input
+        .into_iter()
+        .filter(|s| find_illegal(s.clone()).is_none())
+        .map(find_missing_chain)
+        .map(score_chain)
+        .collect::<Vec<_>>();
+
+    scores.sort();
+    scores[scores.len() / 2]
+}
+
+fn find_missing_chain(row: Vec<char>) -> Vec<char> {
+    let mut stack: Vec<char> = Vec::new();
+    for c in row {
+        match c {
+            '(' | '[' | '{' | '<' => stack.push(c),
+            ']' | ')' | '}' | '>' => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    stack.reverse();
+    
+    // build replacement
+    stack.into_iter().map(|c| {
+        match c {
+            '(' => ')',
+            '[' => ']',
+            '{' => '}',
+            '<' => '>',
+            _ => ' ',
+        }
+    }).collect::<Vec<_>>()
+}
+
+fn score_chain(c: Vec<char>) -> u64 {
+    c.into_iter().fold(0, |acc, c| (5 * acc) + score_char(c))
+}
+
+fn score_char(c: char) -> u64 {
+    match c {
+        ')' => 1,
+        ']' => 2,
+        '}' => 3,
+        '>' => 4,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day10 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 10).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 26397);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 288957);
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert!(matches!(format_input(vec![]), Err(AocError::Parse { .. })));
+    }
+}
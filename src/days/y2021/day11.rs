@@ -0,0 +1,187 @@
+use crate::animate::{on, ramp, Animate, Frame};
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::property::{digit_grid, shrink_digit_grid};
+use crate::render::{Color, Picture, Render};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+
+use std::collections::HashSet;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Grid<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 11;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (1656, 195)
+    }
+}
+
+impl Animate for Day11 {
+    // every step until all the octopuses flash at once, the ones flashing in yellow
+    fn frames(input: &Self::Input) -> Vec<Frame> {
+        let mut frames = vec![energy_frame(input, 0, 0)];
+        let mut grid = input.clone();
+        let everyone = (input.rows() * input.cols()) as u64;
+        // some grids never get there, so we give up at some point
+        for step in 1..=1000 {
+            let (new_grid, flashed) = do_step(grid);
+            frames.push(energy_frame(&new_grid, step, flashed));
+            grid = new_grid;
+            if flashed == everyone {
+                break;
+            }
+        }
+        frames
+    }
+}
+
+fn energy_frame(grid: &Grid<u64>, step: usize, flashed: u64) -> Frame {
+    let lines = grid
+        .iter_rows()
+        .map(|row| {
+            row.map(|&e| {
+                let text = format!("{:>2}", e);
+                if e == 0 && step > 0 {
+                    on(226, &text)
+                } else {
+                    on(ramp(e, 9), &text)
+                }
+            })
+            .collect::<String>()
+        })
+        .collect();
+    Frame {
+        title: format!("day 11, step {}: {} flashed", step, flashed),
+        lines,
+    }
+}
+
+impl Render for Day11 {
+    // energy levels at the start and after the 100 steps of part 1
+    fn pictures(input: &Self::Input) -> Vec<Picture> {
+        let energy = |grid: &Grid<u64>| grid.map(|&e| Color::heat(e, 9));
+        let after = (1..=100).fold(input.clone(), |grid, _| do_step(grid).0);
+        vec![
+            Picture::new("energy", energy(input)),
+            Picture::new("energy-100", energy(&after)),
+        ]
+    }
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Grid<u64>, AocError> {
+    Grid::from_digit_lines(&input)
+}
+
+impl RoundTrip for Day11 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        input.to_string().lines().map(|l| l.to_string()).collect()
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        digit_grid(rng, size)
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_digit_grid(input)
+    }
+}
+
+pub fn part_1(input: Grid<u64>) -> u64 {
+    trace!("input: {:?}", input);
+    (1..=100)
+        .fold((input, 0), |acc, _i| {
+            let (new_grid, flashed) = do_step(acc.0);
+            (new_grid, acc.1 + flashed)
+        })
+        .1
+}
+
+// does the step and returns how many octopus flashed
+fn do_step(input: Grid<u64>) -> (Grid<u64>, u64) {
+    let mut charged: Grid<u64> = input.map(|i| i + 1);
+    let mut flashed: HashSet<Point2> = HashSet::new();
+    let mut has_flashed = true;
+
+    while has_flashed {
+        has_flashed = false;
+        for p in input.points() {
+            // if it needs to flash and it hasn't already
+            if charged[p] > 9 && !flashed.contains(&p) {
+                has_flashed = true;
+                flashed.insert(p);
+
+                // flash neighbours, diagonals included
+                for n in charged.neighbors8(p) {
+                    charged[n] += 1;
+                }
+            }
+        }
+    }
+
+    // set grid to 0 on flashes
+    for &p in &flashed {
+        charged[p] = 0;
+    }
+
+    (charged, flashed.len() as u64)
+}
+
+pub fn part_2(input: Grid<u64>) -> u64 {
+    let mut sync_step = 0;
+    let mut step = 1;
+    let mut grid = input;
+
+    while sync_step == 0 {
+        let (new_grid, _) = do_step(grid);
+        if new_grid.clone().into_vec().into_iter().all(|i| i == 0) {
+            sync_step = step;
+        }
+        step += 1;
+        grid = new_grid;
+    }
+
+    sync_step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day11 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 11).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 1656);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 195);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::helpers::line_error;
+use crate::parser::lines;
+use crate::property::{shrink_vec, vec_of, word};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+
+use std::collections::{HashMap, HashSet};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = HashMap<String, HashSet<String>>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 12;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (226, 3509)
+    }
+}
+
+pub fn format_input(input: Vec<String>) -> Result<HashMap<String, HashSet<String>>, AocError> {
+    let mut map: HashMap<String, HashSet<String>> = HashMap::new();
+    for line in lines(&input) {
+        let (from, to) = line.split_once("-")?;
+        if from.is_empty() || to.is_empty() {
+            return Err(line.error("expected a connection like 'a-b'"));
+        }
+        let key = from.text().to_string();
+        let value = to.text().to_string();
+
+        // we add both directions to the graph
+        map.entry(key.clone())
+            .or_default()
+            .insert(value.clone());
+        map.entry(value).or_default().insert(key);
+    }
+    for cave in [START, END] {
+        if !map.contains_key(cave) {
+            let message = format!("no connection to the '{}' cave", cave);
+            return Err(line_error("", input.len(), &message));
+        }
+    }
+
+    Ok(map)
+}
+
+impl RoundTrip for Day12 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        connections(input)
+            .iter()
+            .map(|(from, to)| format!("{}-{}", from, to))
+            .collect()
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        let mut caves = vec![START.to_string(), END.to_string()];
+        caves.extend(vec_of(rng, 1, size, |rng| word(rng, "abcdeABCDE", 1, 3)));
+        let mut connected = vec_of(rng, 0, size, |rng| {
+            (rng.pick(&caves).clone(), rng.pick(&caves).clone())
+        });
+        // a map is only valid with a way in and out
+        connected.push((START.to_string(), rng.pick(&caves).clone()));
+        connected.push((rng.pick(&caves).clone(), END.to_string()));
+        connect(&connected)
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_vec(&connections(input), 0, |_| Vec::new())
+            .iter()
+            .map(|connected| connect(connected))
+            .filter(|map| map.contains_key(START) && map.contains_key(END))
+            .collect()
+    }
+}
+
+// every connection once, sorted, which is how they are printed
+fn connections(map: &HashMap<String, HashSet<String>>) -> Vec<(String, String)> {
+    let mut connections = map
+        .iter()
+        .flat_map(|(from, to)| {
+            to.iter()
+                .filter(move |to| from <= *to)
+                .map(move |to| (from.clone(), to.clone()))
+        })
+        .collect::<Vec<_>>();
+    connections.sort();
+    connections
+}
+
+// the map `format_input` builds out of some connections
+fn connect(connections: &[(String, String)]) -> HashMap<String, HashSet<String>> {
+    let mut map: HashMap<String, HashSet<String>> = HashMap::new();
+    for (from, to) in connections {
+        map.entry(from.clone()).or_default().insert(to.clone());
+        map.entry(to.clone()).or_default().insert(from.clone());
+    }
+    map
+}
+
+const START: &str = "start";
+const END: &str = "end";
+
+pub fn part_1(input: HashMap<String, HashSet<String>>) -> u64 {
+    trace!("input: {:?}", input);
+    let can_visit: fn(&str, &HashMap<String, u32>) -> bool =
+        |next, visited| !(next.to_lowercase() == *next && visited.contains_key(next));
+    let paths = find_paths(&input, START, &Vec::new(), &HashMap::new(), can_visit);
+
+    paths.len() as u64
+}
+
+fn find_paths(
+    input: &HashMap<String, HashSet<String>>,
+    current: &str,
+    paths: &[String],
+    visited: &HashMap<String, u32>,
+    can_visit: fn(&str, &HashMap<String, u32>) -> bool,
+) -> Vec<String> {
+    // we make a copy of the visited nodes as otherwise a shared structure would make us miss paths
+    let mut visited_copy = visited.clone();
+    *visited_copy.entry(current.to_string()).or_insert(0) += 1;
+
+    // keep track of path for debugging purposes
+    let new_paths = if current == START {
+        vec![(current.to_owned() + ",")]
+    } else {
+        paths
+            .iter()
+            .map(|p| p.to_owned() + current + ",")
+            .collect::<Vec<String>>()
+    };
+
+    // terminate on end
+    if current == END {
+        return new_paths;
+    }
+
+    // otherwise build new paths
+    let mut child_paths: Vec<String> = Vec::new();
+    let next_paths = input.get(current).into_iter().flatten();
+    next_paths.for_each(|next| {
+        if can_visit(next, &visited_copy) {
+            child_paths.extend(find_paths(input, next, &new_paths, &visited_copy, can_visit));
+            trace!("child paths: {:?}", child_paths);
+        }
+    });
+
+    child_paths
+}
+
+pub fn part_2(input: HashMap<String, HashSet<String>>) -> u64 {
+    let can_visit: fn(&str, &HashMap<String, u32>) -> bool = |next, visited| {
+        let not_start = next != START;
+        let not_lowercase = next.to_lowercase() != next;
+
+        let single_lowercase_two_visits = visited
+            .iter()
+            .filter(|&pair| &pair.0.to_lowercase() == pair.0 && pair.1 == &2)
+            .count()
+            <= 1;
+        // we need this to ensure we are not looping between upper and lowercase and having a single lowercase with 400 visits
+        let no_lowercase_more_than_two = visited
+            .iter()
+            .filter(|&pair| &pair.0.to_lowercase() == pair.0 && pair.1 > &2)
+            .count()
+            == 0;
+        let lowercase_allowed = next.to_lowercase() == next
+            && no_lowercase_more_than_two && single_lowercase_two_visits;
+
+        not_start && (not_lowercase || lowercase_allowed)
+    };
+    let paths = find_paths(&input, START, &Vec::new(), &HashMap::new(), can_visit);
+
+    paths.len() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day12 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 12).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 226);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 3509);
+    }
+
+    #[test]
+    fn rejects_missing_caves() {
+        assert!(matches!(
+            format_input(Vec::new()),
+            Err(AocError::Parse { .. })
+        ));
+        assert!(matches!(
+            format_input(vec!["start-a".to_string(), "a-b".to_string()]),
+            Err(AocError::Parse { line: 3, .. })
+        ));
+    }
+}
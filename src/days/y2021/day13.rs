@@ -0,0 +1,307 @@
+use crate::animate::{downscale, Animate, Frame};
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::parser::expect_sections;
+use crate::property::{int, shrink_int, shrink_point, shrink_vec, vec_of};
+use crate::render::{Color, Picture, Render};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::{info, trace};
+
+use std::collections::HashSet;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Entry;
+    type Answer1 = u64;
+    type Answer2 = Grid<String>;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 13;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (17, sample_grid())
+    }
+}
+
+impl Animate for Day13 {
+    // the paper after every fold, shrunk to fit the terminal while it is still big
+    fn frames(input: &Self::Input) -> Vec<Frame> {
+        let mut grid = input.grid.clone();
+        let mut frames = vec![Frame {
+            title: format!("day 13, {} dots", grid.len()),
+            lines: downscale(&grid, 100, 40),
+        }];
+        for (i, ins) in input.instructions.iter().enumerate() {
+            grid = fold(ins, grid);
+            let axis = match ins.direction {
+                Direction::X => "x",
+                Direction::Y => "y",
+            };
+            frames.push(Frame {
+                title: format!(
+                    "day 13, fold {} of {} along {}={}: {} dots",
+                    i + 1,
+                    input.instructions.len(),
+                    axis,
+                    ins.position,
+                    grid.len()
+                ),
+                lines: downscale(&grid, 100, 40),
+            });
+        }
+        frames
+    }
+}
+
+impl Render for Day13 {
+    // the dots on the paper before folding it and once all folds are done
+    fn pictures(input: &Self::Input) -> Vec<Picture> {
+        let folded = input
+            .instructions
+            .iter()
+            .fold(input.grid.clone(), |grid, ins| fold(ins, grid));
+        vec![
+            Picture::new("paper", dots(&input.grid)),
+            Picture::new("folded", dots(&folded)),
+        ]
+    }
+}
+
+fn dots(set: &HashSet<Point2>) -> Grid<Color> {
+    let rows = set.iter().map(|p| p.y + 1).max().unwrap_or(1);
+    let cols = set.iter().map(|p| p.x + 1).max().unwrap_or(1);
+    let mut grid = Grid::init(rows as usize, cols as usize, Color::BLACK);
+    for &p in set.iter() {
+        if grid.contains(p) {
+            grid[p] = Color::WHITE;
+        }
+    }
+    grid
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Direction {
+    X,
+    Y,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub position: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub grid: HashSet<Point2>,
+    pub instructions: Vec<Instruction>,
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Entry, AocError> {
+    let sections = expect_sections(&input, 2, "missing fold instructions")?;
+
+    let grid = sections[0]
+        .spans()
+        .map(|line| {
+            let (x, y) = line.split_once(",")?;
+            Ok(Point2::new(x.value()?, y.value()?))
+        })
+        .collect::<Result<HashSet<_>, AocError>>()?;
+
+    let instructions = sections[1]
+        .spans()
+        .map(|line| {
+            let (axis, position) = line.prefix("fold along ")?.split_once("=")?;
+            let direction = match axis.text() {
+                "x" => Direction::X,
+                "y" => Direction::Y,
+                _ => return Err(axis.error("expected a fold along x or y")),
+            };
+            Ok(Instruction {
+                direction,
+                position: position.value()?,
+            })
+        })
+        .collect::<Result<Vec<_>, AocError>>()?;
+
+    Ok(Entry { grid, instructions })
+}
+
+impl RoundTrip for Day13 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        let mut lines = sorted_dots(&input.grid)
+            .iter()
+            .map(|p| format!("{},{}", p.x, p.y))
+            .collect::<Vec<_>>();
+        lines.push(String::new());
+        lines.extend(input.instructions.iter().map(|ins| {
+            let axis = match ins.direction {
+                Direction::X => "x",
+                Direction::Y => "y",
+            };
+            format!("fold along {}={}", axis, ins.position)
+        }));
+        lines
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        let grid = vec_of(rng, 1, size, |rng| {
+            Point2::new(int(rng, i64::MIN, i64::MAX), int(rng, i64::MIN, i64::MAX))
+        });
+        let instructions = vec_of(rng, 1, size, |rng| Instruction {
+            direction: if rng.chance(2) {
+                Direction::X
+            } else {
+                Direction::Y
+            },
+            position: int(rng, i64::MIN, i64::MAX),
+        });
+        Entry {
+            grid: grid.into_iter().collect(),
+            instructions,
+        }
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        let dots = shrink_vec(&sorted_dots(&input.grid), 1, |&p| shrink_point(p))
+            .into_iter()
+            .map(|dots| Entry {
+                grid: dots.into_iter().collect(),
+                instructions: input.instructions.clone(),
+            });
+        let instructions = shrink_vec(&input.instructions, 1, |ins| {
+            shrink_int(ins.position)
+                .into_iter()
+                .map(|position| Instruction {
+                    position,
+                    ..ins.clone()
+                })
+                .collect()
+        })
+        .into_iter()
+        .map(|instructions| Entry {
+            grid: input.grid.clone(),
+            instructions,
+        });
+        dots.chain(instructions).collect()
+    }
+}
+
+// the dots row by row, so they always print the same way
+fn sorted_dots(grid: &HashSet<Point2>) -> Vec<Point2> {
+    let mut dots = grid.iter().copied().collect::<Vec<_>>();
+    dots.sort_by_key(|p| (p.y, p.x));
+    dots
+}
+
+pub fn part_1(input: Entry) -> u64 {
+    trace!("input: {:?}", input);
+    let result = fold(&input.instructions[0], input.grid);
+    result.len() as u64
+}
+
+fn fold(instruction: &Instruction, grid: HashSet<Point2>) -> HashSet<Point2> {
+    // find any points where x or y > position as they will be removed from the grid
+    let (stay, removed): (HashSet<_>, HashSet<_>) = match instruction.direction {
+        Direction::X => grid.into_iter().partition(|p| p.x < instruction.position),
+        Direction::Y => grid.into_iter().partition(|p| p.y < instruction.position),
+    };
+
+    let mut new_grid = stay;
+
+    // fold any points from the removed section, mirroring them over the fold line
+    for &p in removed.iter() {
+        let folded = match instruction.direction {
+            Direction::X => Point2::new(2 * instruction.position - p.x, p.y),
+            Direction::Y => Point2::new(p.x, 2 * instruction.position - p.y),
+        };
+        new_grid.insert(folded);
+    }
+
+    new_grid
+}
+
+pub fn part_2(input: Entry) -> Grid<String> {
+    let result = input
+        .instructions
+        .into_iter()
+        .fold(input.grid, |grid, ins| fold(&ins, grid));
+    set_to_letters(result)
+}
+
+fn sample_grid() -> Grid<String> {
+    let mut grid = Grid::init(5, 5, ".".to_string());
+    let visible = vec![
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (0, 4),
+        (1, 0),
+        (1, 4),
+        (2, 0),
+        (2, 4),
+        (3, 0),
+        (3, 4),
+        (4, 0),
+        (4, 1),
+        (4, 2),
+        (4, 3),
+        (4, 4),
+    ];
+    for &(x, y) in visible.iter() {
+        grid[Point2::new(x, y)] = "#".to_string();
+    }
+    grid
+}
+
+fn set_to_letters(set: HashSet<Point2>) -> Grid<String> {
+    trace!("dots: {:?}", set);
+    let rows: usize = (set.iter().map(|p| p.y).max().unwrap() + 1) as usize;
+    let cols: usize = (set.iter().map(|p| p.x).max().unwrap() + 1) as usize;
+
+    let mut grid = Grid::init(rows, cols, ".".to_string());
+    for &p in set.iter() {
+        grid[p] = "#".to_string();
+    }
+
+    info!("folded paper:\n{}", grid);
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day13 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 13).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 17);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), sample_grid());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::numeric::{add, sub};
+use crate::parser::{arrow, expect_sections};
+use crate::property::{shrink_vec, vec_of, word};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::{debug, info, trace};
+
+use std::collections::HashMap;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Entry;
+    type Answer1 = u64;
+    type Answer2 = u128;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 14;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (1588, 2188189693529)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub template: Vec<String>,
+    pub pair_insertions: HashMap<String, String>,
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Entry, AocError> {
+    let sections = expect_sections(&input, 2, "missing pair insertion rules")?;
+    let template = sections[0].lines[0]
+        .chars()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>();
+
+    let pair_insertions = sections[1]
+        .spans()
+        .map(|line| {
+            let (pair, element) = arrow(line)?;
+            if pair.text().chars().count() != 2 {
+                return Err(pair.error("expected a pair like 'AB'"));
+            }
+            if element.text().chars().count() != 1 {
+                return Err(element.error("expected a single element"));
+            }
+            Ok((pair.text().to_string(), element.text().to_string()))
+        })
+        .collect::<Result<HashMap<_, _>, AocError>>()?;
+
+    Ok(Entry {
+        template,
+        pair_insertions,
+    })
+}
+
+impl RoundTrip for Day14 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        let mut lines = vec![input.template.concat(), String::new()];
+        lines.extend(
+            sorted_rules(&input.pair_insertions)
+                .iter()
+                .map(|(pair, element)| format!("{} -> {}", pair, element)),
+        );
+        lines
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        const ELEMENTS: &str = "BCHNOPSV";
+        Entry {
+            template: vec_of(rng, 1, size, |rng| word(rng, ELEMENTS, 1, 1)),
+            pair_insertions: vec_of(rng, 1, size, |rng| {
+                (word(rng, ELEMENTS, 2, 2), word(rng, ELEMENTS, 1, 1))
+            })
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        let templates = shrink_vec(&input.template, 1, |_| Vec::new())
+            .into_iter()
+            .map(|template| Entry {
+                template,
+                pair_insertions: input.pair_insertions.clone(),
+            });
+        let rules = shrink_vec(&sorted_rules(&input.pair_insertions), 1, |_| Vec::new())
+            .into_iter()
+            .map(|rules| Entry {
+                template: input.template.clone(),
+                pair_insertions: rules.into_iter().collect(),
+            });
+        templates.chain(rules).collect()
+    }
+}
+
+// the rules by pair, so they always print the same way
+fn sorted_rules(pair_insertions: &HashMap<String, String>) -> Vec<(String, String)> {
+    let mut rules = pair_insertions
+        .iter()
+        .map(|(pair, element)| (pair.clone(), element.clone()))
+        .collect::<Vec<_>>();
+    rules.sort();
+    rules
+}
+
+pub fn part_1(input: Entry) -> u64 {
+    trace!("input: {:?}", input);
+    let steps = 10;
+    let result = (1..=steps).fold(input.template, |acc, _| step(acc, &input.pair_insertions));
+    calculate_value(result)
+}
+
+pub fn step(template: Vec<String>, insertions: &HashMap<String, String>) -> Vec<String> {
+    let mut result = template.clone();
+    let mut to_insert: Vec<(usize, String)> = Vec::new();
+
+    template.windows(2).enumerate().for_each(|(i, pair)| {
+        let key = pair.join("");
+        if let Some(value) = insertions.get(&key) {
+            to_insert.push((i + 1, value.to_string()))
+        }
+    });
+
+    to_insert.sort_by(|a, b| a.0.cmp(&b.0).reverse());
+    to_insert.into_iter().for_each(|(i, s)| {
+        result.insert(i, s);
+    });
+    trace!("polymer of length {}: {}", result.len(), result.concat());
+    result
+}
+
+fn calculate_value(elements: Vec<String>) -> u64 {
+    let mut map: HashMap<String, u64> = HashMap::new();
+
+    elements.into_iter().for_each(|e| {
+        let count = map.entry(e).or_insert(0);
+        *count = add(*count, 1, "element count");
+    });
+
+    let max = map.values().max().unwrap();
+    let min = map.values().min().unwrap();
+
+    sub(*max, *min, "element count difference")
+}
+
+pub fn part_2(input: Entry) -> u128 {
+    // needs a new implementation as the array of elements wouldn't fit in memory
+    let steps = 40;
+
+    let template_as_map = count_pairs(&input.template);
+    let insertions_as_map = split_insertions(&input.pair_insertions);
+    debug!("insertions: {:?}", insertions_as_map);
+
+    let result = (1..=steps).fold(template_as_map, |acc, _| step_map(acc, &insertions_as_map));
+    calculate_value_from_map(result)
+}
+
+pub fn count_pairs(template: &[String]) -> HashMap<String, u128> {
+    let mut pairs: HashMap<String, u128> = HashMap::new();
+    template.windows(2).for_each(|pair| {
+        let key = pair.join("");
+        let count = pairs.entry(key).or_insert(0);
+        *count = add(*count, 1, "pair count");
+    });
+    pairs
+}
+
+// each insertion turns a pair into the two pairs either side of the new element
+pub fn split_insertions(insertions: &HashMap<String, String>) -> HashMap<String, Vec<String>> {
+    insertions
+        .iter()
+        .map(|(k, v)| {
+            let value1 = k.chars().next().unwrap().to_string() + v;
+            let value2 = v.to_string() + &k.chars().nth(1).unwrap().to_string();
+            (k.to_owned(), vec![value1, value2])
+        })
+        .collect()
+}
+
+pub fn step_map(
+    template: HashMap<String, u128>,
+    insertions: &HashMap<String, Vec<String>>,
+) -> HashMap<String, u128> {
+    let mut result: HashMap<String, u128> = HashMap::new();
+
+    template.into_iter().for_each(|(key, count)| {
+        if let Some(values) = insertions.get(&key) {
+            values.iter().for_each(|new_pair| {
+                let total = result.entry(new_pair.to_string()).or_insert(0);
+                *total = add(*total, count, "pair count");
+            });
+        }
+    });
+
+    trace!("pairs: {:?}", result);
+    result
+}
+
+fn calculate_value_from_map(elements: HashMap<String, u128>) -> u128 {
+    let mut map: HashMap<String, u128> = HashMap::new();
+
+    // every pair is split into its components
+    elements.into_iter().for_each(|e| {
+        let mut iterator = e.0.chars();
+        let k1 = iterator.next().unwrap().to_string();
+        let k2 = iterator.next().unwrap().to_string();
+
+        for k in [k1, k2] {
+            let count = map.entry(k).or_insert(0);
+            *count = add(*count, e.1, "element count");
+        }
+    });
+    info!("element counts: {:?}", map);
+
+    // let max = (*map.values().max().unwrap() as f64/2_f64).ceil() as u64;
+    let max = map.values().max().unwrap();
+    let min = map.values().min().unwrap();
+
+    // every letter is counted twice due to a letter belonging to 2 pairs, so divide by 2! Take in account int division
+    let diff = sub(*max, *min, "element count difference");
+    if diff.is_multiple_of(2) {
+        diff / 2
+    } else {
+        diff.div_ceil(2)
+    }
+}
+// 3700829748840
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day14 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 14).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 1588);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 2188189693529);
+    }
+}
@@ -0,0 +1,280 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::property::{digit_grid, shrink_digit_grid};
+use crate::render::{Color, Picture, Render};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Grid<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 15;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (40, 315)
+    }
+}
+
+impl Render for Day15 {
+    // the risk levels, darker the riskier, with the path of lowest total risk from corner to corner
+    fn pictures(input: &Self::Input) -> Vec<Picture> {
+        let target = Point2::new(input.cols() as i64 - 1, input.rows() as i64 - 1);
+        let scores = find_path_lower_risk_part_2(input, Point2::ORIGIN, target);
+        let cells = input.map(|&risk| Color::gray(255 - (risk * 25) as u8));
+        vec![Picture::new("risk", cells).with_path(lowest_risk_path(input, &scores, target))]
+    }
+}
+
+// walks back from the target through the neighbours the score of each cell was reached from
+fn lowest_risk_path(input: &Grid<u64>, scores: &Grid<u64>, target: Point2) -> Vec<Point2> {
+    let mut path = vec![target];
+    let mut current = target;
+    while current != Point2::ORIGIN && path.len() <= input.rows() * input.cols() {
+        let previous = input
+            .neighbors4(current)
+            .filter(|&n| scores[n] != u64::MAX)
+            .min_by_key(|&n| scores[n]);
+        match previous {
+            Some(p) => current = p,
+            None => break,
+        }
+        path.push(current);
+    }
+    path.reverse();
+    path
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Grid<u64>, AocError> {
+    Grid::from_digit_lines(&input)
+}
+
+impl RoundTrip for Day15 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        input.to_string().lines().map(|l| l.to_string()).collect()
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        digit_grid(rng, size)
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_digit_grid(input)
+    }
+}
+
+pub fn part_1(input: Grid<u64>) -> u64 {
+    trace!("input: {:?}", input);
+    let target = Point2::new(input.cols() as i64 - 1, input.rows() as i64 - 1);
+    let map_weights = find_path_lower_risk(&input, Point2::ORIGIN, target);
+    map_weights[target]
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+struct Score {
+    score: u64,
+    position: Point2,
+}
+
+// reverse order so that heap retruns smaller items first
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// reverse order so that heap retruns smaller items first
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.score.cmp(&other.score).reverse()
+    }
+}
+
+// trying to implement Dijkstra with a minor variation in here
+pub fn find_path_lower_risk(
+    input: &Grid<u64>,
+    start: Point2,
+    target: Point2,
+) -> Grid<u64> {
+    let mut visited: HashSet<Point2> = HashSet::new();
+    // initialise score grid with infinity for all positions except origin
+    let mut scores: Grid<u64> = Grid::init(input.rows(), input.cols(), u64::MAX);
+    scores[start] = 0;
+
+    // store scores in a heap (ordered by score) as it will be needed by the algorithm to select the next position to visit
+    let mut visit_next: BinaryHeap<Score> = BinaryHeap::new();
+    for p in scores.points() {
+        visit_next.push(Score {
+            score: scores[p],
+            position: p,
+        });
+    }
+
+    // start with initial position (has weight 0) and visit all neighbours
+    while let Some(Score { score, position }) = visit_next.pop() {
+        trace!("visiting {} with risk {}", position, score);
+        let current_node = position;
+        // exit if we are done
+        if current_node == target {
+            break;
+        }
+
+        let neighbours = input.neighbors4(current_node);
+
+        // update scores
+        for next_node in neighbours {
+            // skip visited nodes
+            if visited.contains(&next_node) {
+                continue;
+            }
+            // get score for node
+            let temptative_score = score + input[next_node];
+            let next_node_score = if temptative_score < scores[next_node] {
+                temptative_score
+            } else {
+                scores[next_node]
+            };
+
+            // update grid and heap with score
+            scores[next_node] = next_node_score;
+            visit_next.push(Score {
+                score: next_node_score,
+                position: next_node,
+            });
+        }
+        // mark current node as visited and remove it from visit_next set
+        visited.insert(current_node);
+        visit_next = visit_next
+            .into_iter()
+            .filter(|s| s.position != current_node)
+            .collect::<BinaryHeap<Score>>();
+    }
+
+    scores
+}
+
+pub fn part_2(input: Grid<u64>) -> u64 {
+    trace!("input: {:?}", input);
+    let expanded_input = expand_input(&input);
+    let target = Point2::new(
+        expanded_input.cols() as i64 - 1,
+        expanded_input.rows() as i64 - 1,
+    );
+    let map_weights = find_path_lower_risk_part_2(&expanded_input, Point2::ORIGIN, target);
+    map_weights[target]
+}
+
+fn expand_input(input: &Grid<u64>) -> Grid<u64> {
+    let mut new_grid = Grid::init(input.rows() * 5, input.cols() * 5, 0);
+
+    let (rows, cols) = (input.rows() as i64, input.cols() as i64);
+    for p in new_grid.points() {
+        let input_p = Point2::new(p.x % cols, p.y % rows);
+        let modifier = ((p.y / rows) + (p.x / cols)) as u64;
+        // formula below is modification on normal modulo so that we circle on 1, not 0
+        new_grid[p] = (input[input_p] + modifier - 1) % 9 + 1;
+    }
+
+    new_grid
+}
+
+// trying to implement A* with a minor variation in here as Dijkstra is too slow for a bigger grid
+// https://en.wikipedia.org/wiki/A*_search_algorithm
+pub fn find_path_lower_risk_part_2(
+    input: &Grid<u64>,
+    start: Point2,
+    target: Point2,
+) -> Grid<u64> {
+    // heuristic for A*, we use simple distance to estimate cheaper nodes
+    fn h(node: Point2, dest: Point2) -> u64 {
+        node.manhattan(dest) as u64
+    }
+
+    // set a heap with the start node and the heuristic for the node
+    let mut open_set: BinaryHeap<Score> = BinaryHeap::new();
+    open_set.push(Score {
+        score: h(start, target),
+        position: start,
+    });
+
+    let mut came_from: HashMap<Point2, Point2> = HashMap::new();
+
+    // initialise score grid with infinity for all positions except origin
+    let mut g_score: Grid<u64> = Grid::init(input.rows(), input.cols(), u64::MAX);
+    g_score[start] = 0;
+
+    while let Some(Score { score: _, position }) = open_set.pop() {
+        let current_node = position;
+        // exit if we are done
+        if current_node == target {
+            break;
+        }
+
+        // remove current node from visit set
+        open_set = open_set
+            .into_iter()
+            .filter(|s| s.position != current_node)
+            .collect::<BinaryHeap<Score>>();
+
+        let neighbours = input.neighbors4(current_node);
+
+        for next_node in neighbours {
+            // check score recorded in grid and cost of moving to next node
+            let tentative_g_score = g_score[current_node] + input[next_node];
+            // if we have improved on neighbour cost, update score and came_from
+            if tentative_g_score < g_score[next_node] {
+                came_from.insert(next_node, current_node);
+                g_score[next_node] = tentative_g_score;
+                // add neighbour to nodes to explore, along heuristic
+                open_set.push(Score {
+                    score: tentative_g_score + h(next_node, target),
+                    position: next_node,
+                });
+            }
+        }
+    }
+
+    g_score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day15 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 15).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 40);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 315);
+    }
+}
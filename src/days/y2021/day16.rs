@@ -0,0 +1,413 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::helpers::*;
+use crate::numeric::{add, from_binary, product, sum};
+use crate::property::{len, shrink_uint, shrink_vec, uint};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::{debug, trace};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Packet;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 16;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (20, 1)
+    }
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Packet, AocError> {
+    let line = first_line(&input)?;
+    let mut bits = String::new();
+    for (c, ch) in line.char_indices() {
+        let nibble = match ch {
+            '0' => "0000",
+            '1' => "0001",
+            '2' => "0010",
+            '3' => "0011",
+            '4' => "0100",
+            '5' => "0101",
+            '6' => "0110",
+            '7' => "0111",
+            '8' => "1000",
+            '9' => "1001",
+            'A' => "1010",
+            'B' => "1011",
+            'C' => "1100",
+            'D' => "1101",
+            'E' => "1110",
+            'F' => "1111",
+            _ => {
+                return Err(AocError::parse(
+                    1,
+                    c + 1,
+                    line,
+                    "expected a hexadecimal digit",
+                ))
+            }
+        };
+        bits.push_str(nibble);
+    }
+    trace!("bits: {}", bits);
+
+    // whatever follows the outermost packet is padding
+    let mut reader = Reader {
+        line,
+        bits,
+        position: 0,
+    };
+    parse_packet(&mut reader)
+}
+
+impl RoundTrip for Day16 {
+    // back to hexadecimal, four bits per digit
+    fn print(input: &Self::Input) -> Vec<String> {
+        let mut bits = encode(input);
+        while !bits.len().is_multiple_of(4) {
+            bits.push('0');
+        }
+        let digits = bits
+            .as_bytes()
+            .chunks(4)
+            .map(|nibble| {
+                let value = nibble
+                    .iter()
+                    .fold(0, |v, &bit| v * 2 + (bit == b'1') as u32);
+                format!("{:X}", value)
+            })
+            .collect::<String>();
+        vec![digits]
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        let depth = len(rng, 0, size / 3);
+        arbitrary_packet(rng, depth)
+    }
+
+    // a subpacket on its own, fewer or smaller subpackets, or a lower value or version
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        let mut smaller = input.children.clone();
+        let min = if input.type_id >= GT { 2 } else { 1 };
+        for children in shrink_vec(&input.children, min, Day16::shrink) {
+            smaller.push(Packet {
+                children,
+                ..input.clone()
+            });
+        }
+        for value in shrink_uint(input.value) {
+            smaller.push(Packet {
+                value,
+                ..input.clone()
+            });
+        }
+        for version in shrink_uint(input.version) {
+            smaller.push(Packet {
+                version,
+                ..input.clone()
+            });
+        }
+        smaller
+    }
+}
+
+fn arbitrary_packet(rng: &mut Rng, depth: usize) -> Packet {
+    let version = rng.below(8);
+    if depth == 0 || rng.chance(3) {
+        return Packet {
+            version,
+            type_id: LITERAL,
+            value: uint(rng, 0, u64::MAX),
+            children: vec![],
+        };
+    }
+    let type_id = *rng.pick(&[SUM, PRODUCT, MIN, MAX, GT, LT, EQ]);
+    let count = if type_id >= GT { 2 } else { len(rng, 1, 3) };
+    Packet {
+        version,
+        type_id,
+        value: 0,
+        children: (0..count)
+            .map(|_| arbitrary_packet(rng, depth - 1))
+            .collect(),
+    }
+}
+
+// The bits of a packet. Subpackets are given by their length in bits for even versions and by their
+// number for odd ones, so printing is the same every time but both ways get used
+fn encode(packet: &Packet) -> String {
+    let mut bits = format!("{:03b}{:03b}", packet.version, packet.type_id);
+    if packet.type_id == LITERAL {
+        let digits = format!("{:b}", packet.value);
+        let digits = format!("{:0>width$}", digits, width = digits.len().div_ceil(4) * 4);
+        let groups = digits.as_bytes().chunks(4).collect::<Vec<_>>();
+        for (i, group) in groups.iter().enumerate() {
+            bits.push(if i + 1 < groups.len() { '1' } else { '0' });
+            bits.push_str(std::str::from_utf8(group).unwrap());
+        }
+    } else {
+        let body = packet.children.iter().map(encode).collect::<String>();
+        if packet.version.is_multiple_of(2) && body.len() < 1 << 15 {
+            bits.push_str(&format!("0{:015b}", body.len()));
+        } else {
+            bits.push_str(&format!("1{:011b}", packet.children.len()));
+        }
+        bits.push_str(&body);
+    }
+    bits
+}
+
+const SUM: u64 = 0;
+const PRODUCT: u64 = 1;
+const MIN: u64 = 2;
+const MAX: u64 = 3;
+const LITERAL: u64 = 4;
+const GT: u64 = 5;
+const LT: u64 = 6;
+const EQ: u64 = 7;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Packet {
+    pub version: u64,
+    pub type_id: u64,
+    // payload may be different for each packet, a value or some children
+    pub value: u64,
+    pub children: Vec<Packet>,
+}
+
+pub fn part_1(input: Packet) -> u64 {
+    // get versions sum
+    sum_versions(&input)
+}
+
+fn sum_versions(packet: &Packet) -> u64 {
+    let children = sum(packet.children.iter().map(sum_versions), "version sum");
+    add(packet.version, children, "version sum")
+}
+
+// The bits of the transmission, read from the start
+struct Reader<'a> {
+    line: &'a str,
+    bits: String,
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, count: usize, what: &str) -> Result<&str, AocError> {
+        if self.bits.len() - self.position < count {
+            return Err(self.error(
+                self.bits.len(),
+                &format!("the transmission ends in the middle of a {}", what),
+            ));
+        }
+        self.position += count;
+        Ok(&self.bits[self.position - count..self.position])
+    }
+
+    // the error points at the hexadecimal digit holding the bit
+    fn error(&self, bit: usize, message: &str) -> AocError {
+        let column = (bit / 4 + 1).min(self.line.len()).max(1);
+        AocError::parse(1, column, self.line, message)
+    }
+}
+
+fn parse_packet(reader: &mut Reader) -> Result<Packet, AocError> {
+    let start = reader.position;
+    let version = to_int(reader.take(3, "version")?, "version");
+    let type_id = to_int(reader.take(3, "type id")?, "type id");
+    let mut value = 0;
+    let mut children = vec![];
+
+    // process payload
+    if type_id == LITERAL {
+        // for a literal value, the payload is the value we just read
+        let mut value_str = String::new();
+        loop {
+            let group = reader.take(5, "literal value")?;
+            value_str.push_str(&group[1..]);
+            if group.starts_with('0') {
+                break;
+            }
+        }
+        value = to_int(&value_str, "literal value");
+        debug!("literal: {}", value);
+    } else if reader.take(1, "length type id")? == "0" {
+        // we have a fix number of bits to process, find how many and parse until we reach them
+        let subpacket_len = to_int(reader.take(15, "subpacket length")?, "subpacket length");
+        debug!("subpacket length: {}", subpacket_len);
+        let end = add(reader.position, subpacket_len as usize, "subpacket end");
+        while reader.position < end {
+            children.push(parse_packet(reader)?);
+        }
+        if reader.position > end {
+            return Err(reader.error(end, "the subpackets are longer than their length says"));
+        }
+    } else {
+        // we have a number of packets, so we need to parse them
+        let number_packets = to_int(
+            reader.take(11, "number of subpackets")?,
+            "number of subpackets",
+        );
+        debug!("number of packets: {}", number_packets);
+        for _ in 0..number_packets {
+            children.push(parse_packet(reader)?);
+        }
+    }
+
+    // comparisons take two subpackets, the other operators at least one
+    let wrong_count = match type_id {
+        LITERAL => false,
+        GT | LT | EQ => children.len() != 2,
+        _ => children.is_empty(),
+    };
+    if wrong_count {
+        return Err(reader.error(
+            start,
+            &format!(
+                "a packet of type {} can't have {} subpackets",
+                type_id,
+                children.len()
+            ),
+        ));
+    }
+
+    let packet = Packet {
+        version,
+        type_id,
+        value,
+        children,
+    };
+    trace!("packet: {:?}", packet);
+    Ok(packet)
+}
+
+fn to_int(s: &str, what: &str) -> u64 {
+    from_binary(s, what)
+}
+
+pub fn part_2(input: Packet) -> u64 {
+    // get sum
+    sum_literals(&input)
+}
+
+// the parser makes sure operators have the subpackets they need
+fn sum_literals(packet: &Packet) -> u64 {
+    match packet.type_id {
+        SUM => sum(packet.children.iter().map(sum_literals), "sum packet"),
+        PRODUCT => product(packet.children.iter().map(sum_literals), "product packet"),
+        MIN => packet.children.iter().map(sum_literals).min().unwrap(),
+        MAX => packet.children.iter().map(sum_literals).max().unwrap(),
+        LITERAL => packet.value,
+        GT => {
+            let left = sum_literals(&packet.children[0]);
+            let right = sum_literals(&packet.children[1]);
+            if left > right {
+                1
+            } else {
+                0
+            }
+        }
+        LT => {
+            let left = sum_literals(&packet.children[0]);
+            let right = sum_literals(&packet.children[1]);
+            if left < right {
+                1
+            } else {
+                0
+            }
+        }
+        EQ => {
+            let left = sum_literals(&packet.children[0]);
+            let right = sum_literals(&packet.children[1]);
+            if left == right {
+                1
+            } else {
+                0
+            }
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day16 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 16).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 20);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 1);
+    }
+
+    #[test]
+    fn rejects_malformed_transmissions() {
+        let error = |hex: &str| match format_input(vec![hex.to_string()]) {
+            Err(AocError::Parse {
+                column, message, ..
+            }) => (column, message),
+            other => panic!("expected an error for {}, got {:?}", hex, other),
+        };
+        assert_eq!(
+            error("D"),
+            (
+                1,
+                "the transmission ends in the middle of a type id".to_string()
+            )
+        );
+        assert_eq!(
+            error("D2FE2"),
+            (
+                5,
+                "the transmission ends in the middle of a literal value".to_string()
+            )
+        );
+        // a minimum of no packets, and a comparison of a single one
+        assert_eq!(
+            error("0A000"),
+            (1, "a packet of type 2 can't have 0 subpackets".to_string())
+        );
+        assert_eq!(
+            error("16004408"),
+            (1, "a packet of type 5 can't have 1 subpackets".to_string())
+        );
+        // 5 bits of subpackets holding one of 11 bits
+        assert_eq!(
+            error("080014408"),
+            (
+                7,
+                "the subpackets are longer than their length says".to_string()
+            )
+        );
+        assert_eq!(
+            part_2(format_input(vec!["C200B40A82".to_string()]).unwrap()),
+            3
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use crate::animate::{colored, on, Animate, Frame};
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::geometry::Point2;
+use crate::helpers::*;
+use crate::parser::{key_range, Span};
+use crate::property::{int, shrink_int};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Target;
+    type Answer1 = i64;
+    type Answer2 = u64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 17;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (45, 112)
+    }
+}
+
+impl Animate for Day17 {
+    // the highest shot that still hits the target, step by step
+    fn frames(input: &Self::Input) -> Vec<Frame> {
+        let target = *input;
+        let Some(velocity) = highest_shot(target) else {
+            return Vec::new();
+        };
+        let mut probe = Probe {
+            position: Point2::ORIGIN,
+            velocity,
+        };
+        let mut trail = vec![probe.position];
+        while !in_target_area(target, &probe) && !missed_target_area(target, &probe) {
+            probe = step(&probe);
+            trail.push(probe.position);
+        }
+
+        // the whole flight and the target are squeezed into the same view
+        let (width, height) = (60, 30);
+        let min_y = trail.iter().map(|p| p.y).min().unwrap().min(target.min_y);
+        let max_y = trail.iter().map(|p| p.y).max().unwrap().max(0);
+        let max_x = trail.iter().map(|p| p.x).max().unwrap().max(target.max_x);
+        let cell = |p: Point2| {
+            let col = p.x * (width - 1) / max_x.max(1);
+            let row = (max_y - p.y) * (height - 1) / (max_y - min_y).max(1);
+            (col as usize, row as usize)
+        };
+
+        let mut background = vec![vec![" ".to_string(); width as usize]; height as usize];
+        for x in target.min_x..=target.max_x {
+            for y in target.min_y..=target.max_y {
+                let (col, row) = cell(Point2::new(x, y));
+                background[row][col] = on(22, " ");
+            }
+        }
+
+        (0..trail.len())
+            .map(|i| {
+                let mut view = background.clone();
+                for &p in &trail[..i] {
+                    let (col, row) = cell(p);
+                    view[row][col] = colored(244, ".");
+                }
+                let (col, row) = cell(trail[i]);
+                view[row][col] = colored(208, "o");
+                Frame {
+                    title: format!("day 17, velocity {}: step {} at {}", velocity, i, trail[i]),
+                    lines: view.into_iter().map(|row| row.concat()).collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+// the starting velocity reaching the highest, among the same ones part 1 tries
+fn highest_shot(target: Target) -> Option<Point2> {
+    let range_x = target.max_x.max(target.min_x) + 10;
+    let range_y = target.max_y.min(target.min_y);
+    (0..range_x)
+        .flat_map(|x| (range_y..-3 * range_y).map(move |y| (x, y)))
+        .filter_map(|(x, y)| does_it_hit(target, x, y).map(|top| (top, x, y)))
+        .max()
+        .map(|(_, x, y)| Point2::new(x, y))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Target {
+    pub min_x: i64,
+    pub max_x: i64,
+    pub min_y: i64,
+    pub max_y: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Probe {
+    pub position: Point2,
+    pub velocity: Point2,
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Target, AocError> {
+    let line = Span::new(first_line(&input)?, 0);
+    let (x_range, y_range) = line.prefix("target area: ")?.split_once(", ")?;
+    let (min_x, max_x) = key_range(x_range, "x")?;
+    let (min_y, max_y) = key_range(y_range, "y")?;
+
+    Ok(Target {
+        min_x,
+        max_x,
+        min_y,
+        max_y,
+    })
+}
+
+impl RoundTrip for Day17 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        vec![format!(
+            "target area: x={}..{}, y={}..{}",
+            input.min_x, input.max_x, input.min_y, input.max_y
+        )]
+    }
+
+    // the ends aren't kept in order, parsing doesn't check they are
+    fn arbitrary(rng: &mut Rng, _size: usize) -> Self::Input {
+        let mut end = || int(rng, i64::MIN, i64::MAX);
+        Target {
+            min_x: end(),
+            max_x: end(),
+            min_y: end(),
+            max_y: end(),
+        }
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        let t = *input;
+        let min_x = shrink_int(t.min_x)
+            .into_iter()
+            .map(|min_x| Target { min_x, ..t });
+        let max_x = shrink_int(t.max_x)
+            .into_iter()
+            .map(|max_x| Target { max_x, ..t });
+        let min_y = shrink_int(t.min_y)
+            .into_iter()
+            .map(|min_y| Target { min_y, ..t });
+        let max_y = shrink_int(t.max_y)
+            .into_iter()
+            .map(|max_y| Target { max_y, ..t });
+        min_x.chain(max_x).chain(min_y).chain(max_y).collect()
+    }
+}
+
+pub fn part_1(input: Target) -> i64 {
+    trace!("input: {:?}", input);
+    // select potential range of speeds, based on target area. Yeah, brute force it
+    let mut initial_speeds: Vec<(i64, i64)> = Vec::new();
+    // we will scan any positive x force as negative x doesn't make sense
+    let range_x = input.max_x.max(input.min_x) + 10;
+    // we scan y from the smallest in the range to three times that (multiply by -3 to remove negative signs)
+    let range_y = input.max_y.min(input.min_y);
+    for x in 0..range_x {
+        for y in range_y..-3 * range_y {
+            initial_speeds.push((x, y));
+        }
+    }
+
+    let mut max_y = i64::MIN;
+    for (vx, vy) in initial_speeds {
+        let hits = does_it_hit(input, vx, vy);
+        match hits {
+            Some(y) => {
+                max_y = max_y.max(y);
+            }
+            None => {
+                // do nothing
+            }
+        }
+    }
+
+    max_y
+}
+
+// returns the max y if the probe hits the area
+fn does_it_hit(target: Target, start_vx: i64, start_vy: i64) -> Option<i64> {
+    let mut probe = Probe {
+        position: Point2::ORIGIN,
+        velocity: Point2::new(start_vx, start_vy),
+    };
+
+    let mut max_y = 0;
+
+    while !in_target_area(target, &probe) && !missed_target_area(target, &probe) {
+        probe = step(&probe);
+        max_y = max_y.max(probe.position.y);
+    }
+
+    trace!(
+        "max y {}, {:?}, in target {}, missed {}",
+        max_y,
+        probe,
+        in_target_area(target, &probe),
+        missed_target_area(target, &probe)
+    );
+
+    if in_target_area(target, &probe) {
+        Some(max_y)
+    } else {
+        None
+    }
+}
+
+fn missed_target_area(target: Target, probe: &Probe) -> bool {
+    let Probe { position, velocity } = probe;
+    (velocity.x > 0 && position.x >= target.max_x) || (velocity.y < 0 && position.y <= target.min_y)
+}
+
+fn in_target_area(target: Target, probe: &Probe) -> bool {
+    let position = probe.position;
+    position.x >= target.min_x
+        && position.x <= target.max_x
+        && position.y >= target.min_y
+        && position.y <= target.max_y
+}
+
+fn step(probe: &Probe) -> Probe {
+    // drag slows the probe down towards 0 horizontally, while gravity keeps pulling it down
+    let drag = Point2::new(probe.velocity.x.signum(), 1);
+    Probe {
+        position: probe.position + probe.velocity,
+        velocity: probe.velocity - drag,
+    }
+}
+
+pub fn part_2(input: Target) -> u64 {
+    trace!("input: {:?}", input);
+    // select potential range of speeds, based on target area. Yeah, brute force it
+    let mut initial_speeds: Vec<(i64, i64)> = Vec::new();
+    // we will scan any positive x force as negative x doesn't make sense
+    let range_x = input.max_x.max(input.min_x) + 10;
+    // we scan y from the smallest in the range to three times that (multiply by -3 to remove negative signs)
+    let range_y = input.max_y.min(input.min_y);
+    for x in 0..range_x {
+        for y in range_y..-3 * range_y {
+            initial_speeds.push((x, y));
+        }
+    }
+
+    let mut count = 0;
+    for (vx, vy) in initial_speeds {
+        let hits = does_it_hit(input, vx, vy);
+        if hits.is_some() {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day17 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 17).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 45);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 112);
+    }
+}
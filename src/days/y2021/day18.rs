@@ -0,0 +1,377 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::helpers::first_line;
+use crate::property::{shrink_uint, shrink_vec, vec_of};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+use self::BranchExploded::*;
+use self::Exploded::*;
+use std::fmt::Display;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Pair>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 18;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (4140, 3993)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Pair {
+    Leaf(u64),
+    Branch(Box<Pair>, Box<Pair>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum BranchExploded {
+    LeftBranch,
+    RightBranch,
+}
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Exploded {
+    Both(u64, u64),
+    Left(u64, BranchExploded),
+    Right(u64, BranchExploded),
+    Exploded,
+    DidNothing,
+}
+
+impl Display for Pair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pair::Leaf(n) => write!(f, "{}", n),
+            Pair::Branch(l, r) => write!(f, "[{},{}]", l, r),
+        }
+    }
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Vec<Pair>, AocError> {
+    first_line(&input)?;
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, s)| parse_pair(s, i))
+        .collect()
+}
+
+impl RoundTrip for Day18 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        input.iter().map(|pair| pair.to_string()).collect()
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        vec_of(rng, 1, size, |rng| arbitrary_pair(rng, 4))
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_vec(input, 1, shrink_pair)
+    }
+}
+
+// numbers only ever have single digits, as the input has no room for more
+fn arbitrary_pair(rng: &mut Rng, depth: u32) -> Pair {
+    if depth == 0 || rng.chance(3) {
+        Pair::Leaf(rng.below(10))
+    } else {
+        Pair::Branch(
+            Box::new(arbitrary_pair(rng, depth - 1)),
+            Box::new(arbitrary_pair(rng, depth - 1)),
+        )
+    }
+}
+
+// either side on its own, or one of them smaller
+fn shrink_pair(pair: &Pair) -> Vec<Pair> {
+    match pair {
+        Pair::Leaf(n) => shrink_uint(*n).into_iter().map(Pair::Leaf).collect(),
+        Pair::Branch(left, right) => {
+            let mut smaller = vec![(**left).clone(), (**right).clone()];
+            smaller.extend(
+                shrink_pair(left)
+                    .into_iter()
+                    .map(|l| Pair::Branch(Box::new(l), right.clone())),
+            );
+            smaller.extend(
+                shrink_pair(right)
+                    .into_iter()
+                    .map(|r| Pair::Branch(left.clone(), Box::new(r))),
+            );
+            smaller
+        }
+    }
+}
+
+pub fn parse_pair(s: &str, index: usize) -> Result<Pair, AocError> {
+    type Chars<'a> = dyn Iterator<Item = (usize, char)> + 'a;
+
+    // The parsing assumes we always have a left side, a comma, and a right side.
+    // Each side can be either another expression or a number, so we run it recursively
+    fn inner_parse(s: &str, index: usize, chars: &mut Chars) -> Result<Pair, AocError> {
+        match chars.next() {
+            Some((_, '[')) => {
+                let first = inner_parse(s, index, chars)?;
+                expect(s, index, chars, ',')?;
+                let second = inner_parse(s, index, chars)?;
+                expect(s, index, chars, ']')?;
+                Ok(Pair::Branch(Box::new(first), Box::new(second)))
+            }
+            Some((c, num)) => num
+                .to_digit(10)
+                .map(|n| Pair::Leaf(n as u64))
+                .ok_or_else(|| AocError::parse(index + 1, c + 1, s, "expected '[' or a digit")),
+            None => Err(AocError::parse(index + 1, s.len() + 1, s, "unexpected end of line")),
+        }
+    }
+
+    fn expect(s: &str, index: usize, chars: &mut Chars, expected: char) -> Result<(), AocError> {
+        match chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((c, _)) => Err(AocError::parse(
+                index + 1,
+                c + 1,
+                s,
+                format!("expected '{}'", expected),
+            )),
+            None => Err(AocError::parse(index + 1, s.len() + 1, s, "unexpected end of line")),
+        }
+    }
+
+    let mut chars = s.char_indices();
+    let pair = inner_parse(s, index, &mut chars)?;
+    if let Some((c, _)) = chars.next() {
+        return Err(AocError::parse(index + 1, c + 1, s, "unexpected characters after the pair"));
+    }
+    Ok(pair)
+}
+
+pub fn part_1(input: Vec<Pair>) -> u64 {
+    trace!("input: {:?}", input);
+    let first_pair = input[0].clone();
+    let sum = input
+        .into_iter()
+        .skip(1)
+        .fold(first_pair, |acc, p| add(&acc, &p));
+
+    magnitude(&sum)
+}
+
+pub fn part_2(input: Vec<Pair>) -> u64 {
+    trace!("input: {:?}", input);
+    let mut max_magnitude = 0;
+    for p in input.clone() {
+        for p2 in input.clone() {
+            let sum = add(&p, &p2);
+            let mag = magnitude(&sum);
+
+            let sum2 = add(&p2, &p);
+            let mag2 = magnitude(&sum2);
+
+            let local_max = mag.max(mag2);
+            if local_max > max_magnitude {
+                max_magnitude = local_max;
+            }
+        }
+    }
+    max_magnitude
+}
+
+pub fn add(l: &Pair, r: &Pair) -> Pair {
+    let mut new_pair = Pair::Branch(Box::new(l.clone()), Box::new(r.clone()));
+
+    let mut keep_going = true;
+    while keep_going {
+        // we use the mutable value to modify the tree in place, as otherwise the recursion gets complicated
+        // we iterate until neither action causes a change to the pair
+        keep_going = explode(&mut new_pair) || splits(&mut new_pair);
+    }
+
+    new_pair
+}
+
+// If any pair is nested inside four pairs, the leftmost such pair explodes.
+// mutating the input parameter to make it simpler to implement
+fn explode(pair: &mut Pair) -> bool {
+    // we need to push numbers up, both left and right side, so we need a tuple. We put it inside an option as some nodes won't
+    // explode and we need a base case (None)
+    fn check_level(pair: &mut Pair, level: usize) -> Exploded {
+        match pair {
+            Pair::Leaf(_) => DidNothing,
+            Pair::Branch(left, right) => {
+                if level == 4 {
+                    // if explode, get numbers from leaves
+                    let l = if let Pair::Leaf(n) = **left { n } else { 0 };
+                    let r = if let Pair::Leaf(n) = **right { n } else { 0 };
+                    // current pair is now a leaf with a 0 value due to the explosion
+                    *pair = Pair::Leaf(0);
+                    Both(l, r)
+                } else {
+                    // we need to keep traversing the tree. We start with the left side, then right if left didn't cause any change
+                    match check_level(left, level + 1) {
+                        Both(l, r) => {
+                            *pair = Pair::Branch(
+                                left.clone(),
+                                Box::new(add_to_closest_on_left_side(right, r)),
+                            );
+
+                            Left(l, LeftBranch)
+                        }
+                        Left(l, lm) => {
+                            // need to filter to ensure we are not hitting the left wall otherwise we add to the left
+                            if lm == RightBranch {
+                                *pair = Pair::Branch(
+                                    Box::new(add_to_closest_on_right_side(left, l)),
+                                    right.clone(),
+                                );
+                                Exploded
+                            } else {
+                                Left(l, lm)
+                            }
+                        }
+                        Right(r, _) => {
+                            *pair = Pair::Branch(
+                                left.clone(),
+                                Box::new(add_to_closest_on_left_side(right, r)),
+                            );
+
+                            Exploded
+                        }
+                        Exploded => Exploded,
+                        DidNothing => match check_level(right, level + 1) {
+                            Both(l, r) => {
+                                *pair = Pair::Branch(
+                                    Box::new(add_to_closest_on_right_side(left, l)),
+                                    right.clone(),
+                                );
+
+                                Right(r, RightBranch)
+                            }
+                            Left(l, _) => {
+                                *pair = Pair::Branch(
+                                    Box::new(add_to_closest_on_right_side(left, l)),
+                                    right.clone(),
+                                );
+
+                                Exploded
+                            }
+                            Right(r, lm) => {
+                                if lm == LeftBranch {
+                                    *pair = Pair::Branch(
+                                        left.clone(),
+                                        Box::new(add_to_closest_on_left_side(right, r)),
+                                    );
+                                    Exploded
+                                } else {
+                                    Right(r, lm)
+                                }
+                            }
+                            Exploded => Exploded,
+                            DidNothing => DidNothing,
+                        },
+                    }
+                }
+            }
+        }
+    }
+
+    !matches!(check_level(pair, 0), Exploded::DidNothing)
+}
+
+fn add_to_closest_on_left_side(pair: &Pair, n: u64) -> Pair {
+    match pair {
+        Pair::Leaf(l) => Pair::Leaf(l + n),
+        Pair::Branch(left, right) => Pair::Branch(
+            Box::new(add_to_closest_on_left_side(left, n)),
+            right.clone(),
+        ),
+    }
+}
+
+fn add_to_closest_on_right_side(pair: &Pair, n: u64) -> Pair {
+    match pair {
+        Pair::Leaf(l) => Pair::Leaf(l + n),
+        Pair::Branch(left, right) => Pair::Branch(
+            left.clone(),
+            Box::new(add_to_closest_on_right_side(right, n)),
+        ),
+    }
+}
+
+// If any regular number is 10 or greater, the leftmost such regular number splits.
+// It will return true if we have done some modification, as we need to know if we need to loop again
+// Using mutability to avoid having to deal with tuples on return
+fn splits(pair: &mut Pair) -> bool {
+    match pair {
+        Pair::Branch(left, right) => {
+            let splitted = splits(left) || splits(right);
+            *pair = Pair::Branch(left.clone(), right.clone());
+            splitted
+        }
+        Pair::Leaf(n) => {
+            let value = *n;
+            if value > 9 {
+                *pair = Pair::Branch(
+                    Box::new(Pair::Leaf(value / 2)),
+                    Box::new(Pair::Leaf(value / 2 + value % 2)),
+                );
+                true
+            } else {
+                false
+            }
+        }
+    }
+}
+
+pub fn magnitude(p: &Pair) -> u64 {
+    match p {
+        Pair::Leaf(n) => *n,
+        Pair::Branch(l, r) => 3 * magnitude(l) + 2 * magnitude(r),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day18 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 18).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 4140);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 3993);
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert!(matches!(format_input(vec![]), Err(AocError::Parse { .. })));
+    }
+}
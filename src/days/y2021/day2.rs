@@ -0,0 +1,127 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::parser::lines;
+use crate::property::{int, shrink_int, shrink_vec, vec_of, word};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<(String, i32)>;
+    type Answer1 = i32;
+    type Answer2 = i64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 2;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (150, 900)
+    }
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Vec<(String, i32)>, AocError> {
+    lines(&input)
+        .map(|line| {
+            let (command, value) = line.split_once(" ")?;
+            Ok((command.text().to_string(), value.value::<i32>()?))
+        })
+        .collect()
+}
+
+impl RoundTrip for Day2 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        input
+            .iter()
+            .map(|(command, amount)| format!("{} {}", command, amount))
+            .collect()
+    }
+
+    // any word parses as a command, not only the ones the submarine knows
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        vec_of(rng, 0, size, |rng| {
+            let command = match rng.below(4) {
+                0 => "forward".to_string(),
+                1 => "down".to_string(),
+                2 => "up".to_string(),
+                _ => word(rng, "abcdefghijklmnopqrstuvwxyz", 1, 8),
+            };
+            (command, int(rng, i32::MIN, i32::MAX))
+        })
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_vec(input, 0, |(command, amount)| {
+            shrink_int(*amount)
+                .into_iter()
+                .map(|amount| (command.clone(), amount))
+                .collect()
+        })
+    }
+}
+
+pub fn part_1(input: Vec<(String, i32)>) -> i32 {
+    let mut horizontal = 0;
+    let mut vertical = 0;
+    input.iter().for_each(|(c, i)| {
+        if c == "forward" {
+            horizontal += i;
+        } else if c == "down" {
+            vertical += i;
+        } else if c == "up" {
+            vertical -= i;
+        }
+    });
+    horizontal * vertical
+}
+
+pub fn part_2(input: Vec<(String, i32)>) -> i64 {
+    let mut horizontal: i64 = 0;
+    let mut vertical: i64 = 0;
+    let mut aim: i64 = 0;
+    input.iter().for_each(|(c, i)| {
+        let i = *i as i64;
+        if c == "forward" {
+            horizontal += i;
+            vertical += aim * i;
+        } else if c == "down" {
+            aim += i;
+        } else if c == "up" {
+            aim -= i;
+        }
+    });
+    horizontal * vertical
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day2 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 2).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 150);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 900);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::helpers::*;
+use crate::info;
+use crate::numeric::{add, convert, from_binary, mul};
+use crate::property::{len, shrink_vec, vec_of, word};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = i32;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 3;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (198, 230)
+    }
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Vec<String>, AocError> {
+    first_line(&input)?;
+    for (i, line) in input.iter().enumerate() {
+        if line.is_empty() {
+            return Err(AocError::parse(i + 1, 1, line, "expected a binary number"));
+        }
+        if let Some((c, _)) = line.char_indices().find(|&(_, ch)| ch != '0' && ch != '1') {
+            return Err(AocError::parse(i + 1, c + 1, line, "expected only binary digits"));
+        }
+        if line.len() != input[0].len() {
+            return Err(line_error(line, i, "all numbers must have the same length"));
+        }
+    }
+    Ok(input)
+}
+
+impl RoundTrip for Day3 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        input.clone()
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        let width = len(rng, 1, 16);
+        vec_of(rng, 1, size, |rng| word(rng, "01", width, width))
+    }
+
+    // fewer numbers, or all of them without their last bit
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        let mut smaller = shrink_vec(input, 1, |_| Vec::new());
+        if input.iter().all(|line| line.len() > 1) {
+            smaller.push(
+                input
+                    .iter()
+                    .map(|line| line[..line.len() - 1].to_string())
+                    .collect(),
+            );
+        }
+        smaller
+    }
+}
+
+pub fn part_1(input: Vec<String>) -> u32 {
+    let mut gamma = vec![0; input[0].len()];
+    let mut epsilon = vec![0; input[0].len()];
+    input.iter().for_each(|s| {
+        s.chars().enumerate().for_each(|(i, c)| {
+            let v = c.to_digit(2).unwrap();
+            gamma[i] = add(gamma[i], v, "bit count");
+            epsilon[i] = add(epsilon[i], v, "bit count");
+        });
+    });
+    let gamma_rate = process_array(gamma, input.len(), |i, l| if i > l { "1" } else { "0" });
+    let epsilon_rate = process_array(epsilon, input.len(), |i, l| if i < l { "1" } else { "0" });
+    info!("gamma rate: {}", gamma_rate);
+    info!("epsilon rate: {}", epsilon_rate);
+    mul(gamma_rate, epsilon_rate, "power consumption")
+}
+
+// we have added all teh 1 in each position of the array, now we see if they are the majority (sum > length/2)
+// to decide if we make it a 0 or 1
+fn process_array(arr: Vec<u32>, len: usize, cond: fn(u32, u32) -> &'static str) -> u32 {
+    // decide if array position is 0 or 1
+    let binary = arr
+        .into_iter()
+        .map(|i| cond(i, (len as u32) / 2))
+        .collect::<Vec<_>>()
+        .join("");
+    to_int(binary)
+}
+
+pub fn part_2(input: Vec<String>) -> i32 {
+    fn filter_input_oxygen(input: Vec<String>, pos: u32) -> String {
+        let most_common = find_most_common_in_position(&input, pos);
+        let filtered: Vec<String> = input
+            .into_iter()
+            .filter(|s| s.chars().nth(pos as usize).unwrap() == most_common)
+            .collect();
+        if filtered.len() == 1 {
+            filtered[0].clone()
+        } else {
+            filter_input_oxygen(filtered, pos + 1)
+        }
+    }
+
+    fn filter_input_co2(input: Vec<String>, pos: u32) -> String {
+        let less_common = find_less_common_in_position(&input, pos);
+        let filtered: Vec<String> = input
+            .into_iter()
+            .filter(|s| s.chars().nth(pos as usize).unwrap() == less_common)
+            .collect();
+        if filtered.len() == 1 {
+            filtered[0].clone()
+        } else {
+            filter_input_co2(filtered, pos + 1)
+        }
+    }
+
+    let oxygen_binary = filter_input_oxygen(input.clone(), 0);
+    let oxygen = to_int(oxygen_binary);
+
+    let co2_binary = filter_input_co2(input, 0);
+    let co2 = to_int(co2_binary);
+
+    info!("oxygen: {}", oxygen);
+    info!("co2: {}", co2);
+    convert(mul(oxygen, co2, "life support rating"), "life support rating")
+}
+
+fn to_int(s: String) -> u32 {
+    from_binary(&s, "binary number")
+}
+
+fn find_most_common_in_position(input: &[String], pos: u32) -> char {
+    let mut counts = [0; 2];
+    input.iter().for_each(|s| {
+        let v = s
+            .chars()
+            .nth(pos as usize)
+            .map(|c| c.to_digit(2).unwrap())
+            .unwrap();
+        counts[v as usize] = add(counts[v as usize], 1u32, "bit count");
+    });
+    if counts[0] > counts[1] {
+        '0'
+    } else {
+        '1'
+    }
+}
+
+fn find_less_common_in_position(input: &[String], pos: u32) -> char {
+    let mut counts = [0; 2];
+    input.iter().for_each(|s| {
+        let v = s
+            .chars()
+            .nth(pos as usize)
+            .map(|c| c.to_digit(2).unwrap())
+            .unwrap();
+        counts[v as usize] = add(counts[v as usize], 1u32, "bit count");
+    });
+    if counts[0] <= counts[1] {
+        '0'
+    } else {
+        '1'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day3 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 3).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 198);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 230);
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert!(matches!(format_input(vec![]), Err(AocError::Parse { .. })));
+    }
+
+    #[test]
+    fn rejects_empty_numbers() {
+        assert!(matches!(
+            format_input(vec![String::new()]),
+            Err(AocError::Parse {
+                line: 1,
+                column: 1,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,301 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::helpers::line_error;
+use crate::parser::{comma_separated, expect_sections};
+use crate::property::{int, shrink_int, shrink_vec, vec_of};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Data;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 4;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (4512, 1924)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Board {
+    pub lines: Vec<Vec<i32>>,
+}
+
+impl Clone for Board {
+    fn clone(&self) -> Board {
+        Board {
+            lines: self.lines.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Data {
+    pub lines: Vec<i32>,
+    pub boards: Vec<Board>,
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Data, AocError> {
+    let sections = expect_sections(&input, 1, "missing the drawn numbers")?;
+    let numbers = sections[0].spans().next().unwrap();
+    let lines = comma_separated::<i32>(numbers)?;
+    if sections.len() < 2 {
+        return Err(line_error("", input.len(), "missing the boards"));
+    }
+
+    let boards = sections[1..]
+        .iter()
+        .map(|section| {
+            trace!("section: {:?}", section);
+            let lines = section
+                .spans()
+                .map(|row| {
+                    let numbers = row
+                        .words()
+                        .iter()
+                        .map(|n| n.value::<i32>())
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if numbers.len() != 5 {
+                        return Err(row.error("a board row needs 5 numbers"));
+                    }
+                    Ok(numbers)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            // point at the first row too many, or at the last one when the board is short
+            if lines.len() != 5 {
+                let row = section.spans().nth(5).or(section.spans().last()).unwrap();
+                return Err(row.error("a board needs 5 rows"));
+            }
+            Ok(Board { lines })
+        })
+        .collect::<Result<Vec<_>, AocError>>()?;
+
+    Ok(Data { lines, boards })
+}
+
+impl RoundTrip for Day4 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        let numbers = input
+            .lines
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>();
+        let mut lines = vec![numbers.join(",")];
+        for board in &input.boards {
+            lines.push(String::new());
+            lines.extend(board.lines.iter().map(|row| {
+                let row = row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>();
+                row.join(" ")
+            }));
+        }
+        lines
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        let number = |rng: &mut Rng| int(rng, i32::MIN, i32::MAX);
+        let lines = vec_of(rng, 1, size, number);
+        let boards = vec_of(rng, 1, size, |rng| Board {
+            lines: (0..5)
+                .map(|_| (0..5).map(|_| number(rng)).collect())
+                .collect(),
+        });
+        Data { lines, boards }
+    }
+
+    // fewer numbers drawn or boards, or a smaller number in either
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        let lines = shrink_vec(&input.lines, 1, |&n| shrink_int(n))
+            .into_iter()
+            .map(|lines| Data {
+                lines,
+                boards: input.boards.clone(),
+            });
+        let boards = shrink_vec(&input.boards, 1, |board| {
+            shrink_vec(&board.lines, 5, |row| {
+                shrink_vec(row, 5, |&n| shrink_int(n))
+            })
+            .into_iter()
+            .map(|lines| Board { lines })
+            .collect()
+        })
+        .into_iter()
+        .map(|boards| Data {
+            lines: input.lines.clone(),
+            boards,
+        });
+        lines.chain(boards).collect()
+    }
+}
+
+pub fn part_1(input: Data) -> i32 {
+    check_boards(input.boards, input.lines)
+}
+
+const MARK: i32 = -1;
+
+fn check_boards(boards: Vec<Board>, numbers: Vec<i32>) -> i32 {
+    let mut found: Option<Board> = None;
+    if let Some((current, remaining_numbers)) = numbers.split_first() {
+        let updated_boards: Vec<Board> = boards
+            .iter()
+            .map(|b| {
+                let lines: Vec<Vec<i32>> = b
+                    .lines
+                    .iter()
+                    .map(|l| {
+                        l.iter()
+                            .map(|i| if i == current { MARK } else { *i })
+                            .collect()
+                    })
+                    .collect();
+                let new_board = Board {
+                    lines: lines.clone(),
+                };
+                if check_winner(lines) {
+                    found = Some(new_board.clone())
+                };
+                new_board
+            })
+            .collect();
+
+        match found {
+            None => check_boards(updated_boards, remaining_numbers.to_vec()),
+            Some(b) => {
+                b.lines
+                    .iter()
+                    .map(|l| l.iter().filter(|i| **i != MARK).sum::<i32>())
+                    .sum::<i32>()
+                    * current
+            }
+        }
+    } else {
+        -1
+    }
+}
+
+fn check_winner(lines: Vec<Vec<i32>>) -> bool {
+    let has_row = lines.iter().any(|l| l.iter().sum::<i32>() == MARK * 5);
+    let has_column = matrix_transpose(&lines)
+        .iter()
+        .any(|l| l.iter().sum::<i32>() == MARK * 5);
+    has_row || has_column
+}
+
+fn matrix_transpose(lines: &[Vec<i32>]) -> Vec<Vec<i32>> {
+    let mut transposed = Vec::new();
+    for i in 0..lines[0].len() {
+        let mut row = Vec::new();
+        for line in lines {
+            row.push(line[i]);
+        }
+        transposed.push(row);
+    }
+    transposed
+}
+
+pub fn part_2(input: Data) -> i32 {
+    check_loser_board(input.boards, input.lines)
+}
+
+fn check_loser_board(boards: Vec<Board>, numbers: Vec<i32>) -> i32 {
+    let mut found: Option<Board> = None;
+    if let Some((current, remaining_numbers)) = numbers.split_first() {
+        let updated_boards: Vec<Board> = boards
+            .iter()
+            .map(|b| {
+                let lines: Vec<Vec<i32>> = b
+                    .lines
+                    .iter()
+                    .map(|l| {
+                        l.iter()
+                            .map(|i| if i == current { MARK } else { *i })
+                            .collect()
+                    })
+                    .collect();
+                let new_board = Board {
+                    lines: lines.clone(),
+                };
+                // only store the winner if we are on the last board
+                if check_winner(lines) && boards.len() == 1 {
+                    found = Some(new_board.clone())
+                };
+                new_board
+            })
+            // remove winners for next iterations
+            .filter(|b| !check_winner(b.lines.clone()))
+            .collect();
+
+        match found {
+            None => check_loser_board(updated_boards, remaining_numbers.to_vec()),
+            Some(b) => {
+                b.lines
+                    .iter()
+                    .map(|l| l.iter().filter(|i| **i != MARK).sum::<i32>())
+                    .sum::<i32>()
+                    * current
+            }
+        }
+    } else {
+        -1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day4 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 4).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 4512);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 1924);
+    }
+
+    #[test]
+    fn rejects_boards_of_wrong_shape() {
+        let mut lines = sample_data(&resources_root(None), 2021, 4).unwrap();
+        let row = lines.remove(4);
+        assert!(matches!(
+            format_input(lines.clone()),
+            Err(AocError::Parse { line: 6, .. })
+        ));
+        lines.insert(4, row.clone());
+        lines.insert(4, row);
+        assert!(matches!(
+            format_input(lines),
+            Err(AocError::Parse {
+                line: 8,
+                column: 1,
+                ..
+            })
+        ));
+        assert!(format_input(vec!["1,2,3".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::parser::{arrow, lines, Span};
+use crate::property::{int, shrink_point, shrink_vec, vec_of};
+use crate::render::{Color, Picture, Render};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+
+use std::collections::HashMap;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<Segment>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 5;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (5, 12)
+    }
+}
+
+impl Render for Day5 {
+    // how many lines cover each point, diagonals included
+    fn pictures(input: &Self::Input) -> Vec<Picture> {
+        let mut point_map: HashMap<Point2, u64> = HashMap::new();
+        input.iter().flat_map(|s| get_points(*s, true)).for_each(|p| {
+            *point_map.entry(p).or_insert(0) += 1;
+        });
+
+        // the picture starts at the smallest coordinates, which may be negative
+        let min_x = point_map.keys().map(|p| p.x).min().unwrap_or(0);
+        let min_y = point_map.keys().map(|p| p.y).min().unwrap_or(0);
+        let rows = point_map.keys().map(|p| p.y - min_y + 1).max().unwrap_or(1);
+        let cols = point_map.keys().map(|p| p.x - min_x + 1).max().unwrap_or(1);
+        let max = point_map.values().copied().max().unwrap_or(1);
+        let mut cells = Grid::init(rows as usize, cols as usize, Color::BLACK);
+        for (p, count) in point_map {
+            cells[Point2::new(p.x - min_x, p.y - min_y)] = Color::heat(count, max);
+        }
+        vec![Picture::new("overlaps", cells)]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: Point2,
+    pub end: Point2,
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Vec<Segment>, AocError> {
+    lines(&input)
+        .map(|line| {
+            trace!("line: {:?}", line);
+            let (start, end) = arrow(line)?;
+            Ok(Segment {
+                start: parse_point(start)?,
+                end: parse_point(end)?,
+            })
+        })
+        .collect()
+}
+
+impl RoundTrip for Day5 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        input
+            .iter()
+            .map(|s| format!("{},{} -> {},{}", s.start.x, s.start.y, s.end.x, s.end.y))
+            .collect()
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        // the real inputs stay below 1000, and points anywhere in i64 overflow the segment lengths
+        let point = |rng: &mut Rng| Point2::new(int(rng, 0, 999), int(rng, 0, 999));
+        vec_of(rng, 0, size, |rng| Segment {
+            start: point(rng),
+            end: point(rng),
+        })
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_vec(input, 0, |&s| {
+            let starts = shrink_point(s.start)
+                .into_iter()
+                .map(|start| Segment { start, ..s });
+            let ends = shrink_point(s.end)
+                .into_iter()
+                .map(|end| Segment { end, ..s });
+            starts.chain(ends).collect()
+        })
+    }
+}
+
+fn parse_point(span: Span) -> Result<Point2, AocError> {
+    let (x, y) = span.split_once(",")?;
+    Ok(Point2::new(x.value()?, y.value()?))
+}
+
+pub fn part_1(input: Vec<Segment>) -> i32 {
+    trace!("input: {:?}", input);
+    let mut point_map: HashMap<Point2, i32> = HashMap::new();
+
+    input.iter().flat_map(|s| get_points(*s, false)).for_each(|p| {
+        let new_count = point_map.get(&p).unwrap_or(&0) + 1;
+        point_map.insert(p, new_count);
+    });
+
+    point_map.values().filter(|&v| *v >= 2).count() as i32
+}
+
+fn get_points(s: Segment, consider_diagonals: bool) -> Vec<Point2> {
+    let delta = s.end - s.start;
+    // diagonals are always at 45 degrees, so segments move one unit per step on each changing axis
+    if delta.x != 0 && delta.y != 0 && !consider_diagonals {
+        return Vec::new();
+    }
+    let direction = delta.signum();
+
+    (0..=s.start.chebyshev(s.end))
+        .map(|i| s.start + direction * i)
+        .collect()
+}
+
+pub fn part_2(input: Vec<Segment>) -> i32 {
+    let mut point_map: HashMap<Point2, i32> = HashMap::new();
+
+    input.iter().flat_map(|s| get_points(*s, true)).for_each(|p| {
+        let new_count = point_map.get(&p).unwrap_or(&0) + 1;
+        point_map.insert(p, new_count);
+    });
+
+    point_map.values().filter(|&v| *v >= 2).count() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day5 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 5).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 5);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 12);
+    }
+
+    #[test]
+    fn renders_negative_coordinates() {
+        let input = format_input(vec!["0,-1 -> 2,-3".to_string()]).unwrap();
+        let pictures = Day5::pictures(&input);
+        assert_eq!(pictures[0].cells.rows(), 3);
+        assert_eq!(pictures[0].cells.cols(), 3);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::helpers::*;
+use crate::numeric::{add, sum};
+use crate::parser::{comma_separated, Span};
+use crate::property::{int, shrink_int, shrink_vec, vec_of};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+
+use std::collections::HashMap;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<u32>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 6;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (5934, 26984457539)
+    }
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Vec<u32>, AocError> {
+    comma_separated(Span::new(first_line(&input)?, 0))
+}
+
+impl RoundTrip for Day6 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        let fish = input.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+        vec![fish.join(",")]
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        vec_of(rng, 1, size, |rng| int(rng, 0, u32::MAX))
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_vec(input, 1, |&f| shrink_int(f))
+    }
+}
+
+pub fn part_1(input: Vec<u32>) -> u64 {
+    trace!("input: {:?}", input);
+    let days = 80;
+    let mut mem_children: HashMap<u32, u64> = HashMap::new();
+    let original_fish: u64 = input.len() as u64;
+    let children = sum(
+        input
+            .into_iter()
+            .map(|i| simulate(i, 0, days, &mut mem_children)),
+        "fish count",
+    );
+    add(original_fish, children, "fish count")
+}
+
+pub fn simulate(
+    initial_state: u32,
+    start_date: u32,
+    up_to_days: u32,
+    mem_children: &mut HashMap<u32, u64>,
+) -> u64 {
+    trace!(
+        "simulating {} from day {} to {}, {} remembered",
+        initial_state,
+        start_date,
+        up_to_days,
+        mem_children.len()
+    );
+    // build array with all the spawn days we will have children on
+    let mut spawn_days: Vec<u32> = Vec::new();
+    let first_spawn = add(start_date, initial_state, "spawn day");
+    if first_spawn < up_to_days {
+        // if we can have children, add to vector the days in which we do so
+        let mut left_days = add(first_spawn, 1, "spawn day");
+        spawn_days.push(left_days);
+        while add(left_days, 7, "spawn day") <= up_to_days {
+            left_days = add(left_days, 7, "spawn day");
+            spawn_days.push(left_days);
+        }
+    }
+    let direct_children = spawn_days.len() as u64;
+
+    // find second order children by calculating the number of children from our children on each particular day
+    let mut second_order_children: u64 = 0;
+    spawn_days.into_iter().for_each(|day| {
+        if mem_children.contains_key(&day) {
+            second_order_children = add(second_order_children, mem_children[&day], "fish count");
+        } else {
+            // data missing, calculate how many fish spawn from a fish born on this day
+            let count = simulate(8, day, up_to_days, mem_children);
+            mem_children.insert(day, count);
+            second_order_children = add(second_order_children, count, "fish count");
+        }
+    });
+
+    trace!(
+        "children: {} direct, {} after",
+        direct_children,
+        second_order_children
+    );
+    add(direct_children, second_order_children, "fish count")
+}
+
+// the straightforward way: count the fish with each timer value and move them along a day at a time
+pub fn simulate_direct(input: &[u32], days: u32) -> u64 {
+    let mut timers = [0u64; 9];
+    input
+        .iter()
+        .for_each(|&t| timers[t as usize] = add(timers[t as usize], 1, "fish count"));
+    for _ in 0..days {
+        timers.rotate_left(1);
+        timers[6] = add(timers[6], timers[8], "fish count");
+    }
+    sum(timers, "fish count")
+}
+
+pub fn part_2(input: Vec<u32>) -> u64 {
+    let days = 256;
+    let mut mem_children: HashMap<u32, u64> = HashMap::new();
+    let original_fish: u64 = input.len() as u64;
+    let children = sum(
+        input
+            .into_iter()
+            .map(|i| simulate(i, 0, days, &mut mem_children)),
+        "fish count",
+    );
+    add(original_fish, children, "fish count")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day6 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 6).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 5934);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 26984457539);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::helpers::*;
+use crate::parser::{comma_separated, Span};
+use crate::property::{shrink_uint, shrink_vec, uint, vec_of};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 7;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (37, 168)
+    }
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Vec<u64>, AocError> {
+    comma_separated(Span::new(first_line(&input)?, 0))
+}
+
+impl RoundTrip for Day7 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        let crabs = input.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        vec![crabs.join(",")]
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        vec_of(rng, 1, size, |rng| uint(rng, 0, u64::MAX))
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_vec(input, 1, |&c| shrink_uint(c))
+    }
+}
+
+pub fn part_1(input: Vec<u64>) -> u64 {
+    trace!("input: {:?}", input);
+    let mut input_clone = input.clone();
+    let med = median(&mut input_clone);
+
+    input
+        .into_iter()
+        .map(|i| i.abs_diff(med))
+        .sum()
+}
+
+fn median(numbers: &mut [u64]) -> u64 {
+    numbers.sort();
+    let mid = numbers.len() / 2;
+    numbers[mid]
+}
+
+pub fn part_2(input: Vec<u64>) -> u64 {
+    let min_pos = input.clone().into_iter().min().unwrap();
+    let max_pos = input.clone().into_iter().max().unwrap();
+
+    // we brute force it because, why not?
+    let mut max_fuel: u64 = u64::MAX;
+    (min_pos..=max_pos).for_each(|i| {
+        let current_sum: u64 = input
+            .clone()
+            .into_iter()
+            .map(|h| i.abs_diff(h))
+            .map(|h| h*(h+1)/2)
+            .sum();
+        if current_sum < max_fuel {
+            max_fuel = current_sum;
+        }
+    });
+
+    max_fuel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day7 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 7).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 37);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 168);
+    }
+}
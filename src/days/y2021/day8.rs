@@ -0,0 +1,231 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::parser::{lines, Span};
+use crate::property::{shrink_vec, vec_of, word};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+
+use std::collections::HashSet;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Entry>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 8;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (26, 61229)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub input: Vec<String>,
+    pub output: Vec<String>,
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Vec<Entry>, AocError> {
+    let words = |span: Span| {
+        span.words()
+            .iter()
+            .map(|w| w.text().to_string())
+            .collect::<Vec<String>>()
+    };
+
+    lines(&input)
+        .map(|line| {
+            let (patterns, output) = line.split_once("|")?;
+            Ok(Entry {
+                input: words(patterns),
+                output: words(output),
+            })
+        })
+        .collect()
+}
+
+impl RoundTrip for Day8 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        input
+            .iter()
+            .map(|e| format!("{} | {}", e.input.join(" "), e.output.join(" ")))
+            .collect()
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        let pattern = |rng: &mut Rng| word(rng, "abcdefg", 1, 7);
+        vec_of(rng, 0, size, |rng| Entry {
+            input: vec_of(rng, 0, 10, pattern),
+            output: vec_of(rng, 0, 4, pattern),
+        })
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_vec(input, 0, |e| {
+            let inputs = shrink_vec(&e.input, 0, |_| Vec::new())
+                .into_iter()
+                .map(|input| Entry {
+                    input,
+                    output: e.output.clone(),
+                });
+            let outputs = shrink_vec(&e.output, 0, |_| Vec::new())
+                .into_iter()
+                .map(|output| Entry {
+                    input: e.input.clone(),
+                    output,
+                });
+            inputs.chain(outputs).collect()
+        })
+    }
+}
+
+pub fn part_1(input: Vec<Entry>) -> u64 {
+    trace!("input: {:?}", input);
+    input
+        .into_iter()
+        .map(|entry| {
+            entry
+                .output
+                .into_iter()
+                .filter(|s| is_one(s) || is_four(s) || is_seven(s) || is_eight(s))
+                .count() as u64
+        })
+        .sum()
+}
+
+pub fn part_2(input: Vec<Entry>) -> u64 {
+    input
+        .into_iter()
+        .map(|entry| {
+            // calculate digits for this row
+            let coded_numbers = decode_numbers(entry.input);
+            // convert output to number
+            let number_as_string = entry
+                .output
+                .into_iter()
+                .map(|s| {
+                    let element_sorted = sort_string(s);
+
+                    coded_numbers
+                        .iter()
+                        .position(|s| *s == element_sorted)
+                        .unwrap()
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+                .join("");
+
+            number_as_string.parse::<u64>().unwrap()
+        })
+        .sum()
+}
+
+fn decode_numbers(input: Vec<String>) -> Vec<String> {
+    let mut coded_numbers = vec!["".to_string(); 10];
+    let sorted_chars_input = input
+        .into_iter()
+        .map(sort_string)
+        .collect::<Vec<_>>();
+    // find 1,4,7,8 - easy numbers
+    for s in sorted_chars_input.iter() {
+        if is_one(s) {
+            coded_numbers[1] = s.clone();
+        } else if is_four(s) {
+            coded_numbers[4] = s.clone();
+        } else if is_seven(s) {
+            coded_numbers[7] = s.clone();
+        } else if is_eight(s) {
+            coded_numbers[8] = s.clone();
+        }
+    }
+    let one_set = coded_numbers[1].chars().collect::<HashSet<_>>();
+    let four_set = coded_numbers[4].chars().collect::<HashSet<_>>();
+    let seven_set = coded_numbers[7].chars().collect::<HashSet<_>>();
+
+    // second iteration for the harder numbers
+    for s in sorted_chars_input.iter() {
+        // find 2,3,5 as they have length 5
+        let input_set = s.chars().collect::<HashSet<_>>();
+        if s.len() == 5 {
+            if input_set.intersection(&four_set).count() == 2 {
+                coded_numbers[2] = s.clone();
+            } else if input_set.intersection(&seven_set).count() == 3 {
+                coded_numbers[3] = s.clone();
+            } else {
+                coded_numbers[5] = s.clone();
+            }
+        }
+        // find 0, 6, 9 as they have length 6
+        if s.len() == 6 {
+            let input_set = s.chars().collect::<HashSet<_>>();
+            if input_set.intersection(&one_set).count() == 1 {
+                coded_numbers[6] = s.clone();
+            } else if input_set.intersection(&four_set).count() == 4 {
+                coded_numbers[9] = s.clone();
+            } else {
+                coded_numbers[0] = s.clone();
+            }
+        }
+    }
+
+    coded_numbers
+}
+
+fn sort_string(s: String) -> String {
+    let mut sorted = s.chars().collect::<Vec<char>>();
+    sorted.sort();
+    String::from_iter(&sorted)
+}
+
+fn is_one(s: &str) -> bool {
+    s.len() == 2
+}
+
+fn is_four(s: &str) -> bool {
+    s.len() == 4
+}
+
+fn is_seven(s: &str) -> bool {
+    s.len() == 3
+}
+
+fn is_eight(s: &str) -> bool {
+    s.len() == 7
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day8 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 8).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 26);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 61229);
+    }
+}
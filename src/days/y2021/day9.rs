@@ -0,0 +1,159 @@
+use crate::error::AocError;
+use crate::generate::Rng;
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::property::{digit_grid, shrink_digit_grid};
+use crate::render::{Color, Picture, Render};
+use crate::roundtrip::RoundTrip;
+use crate::solution::Solution;
+use crate::trace;
+use std::collections::HashSet;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Grid<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 9;
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError> {
+        format_input(input)
+    }
+
+    fn part_1(input: Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+
+    fn sample_answers() -> (Self::Answer1, Self::Answer2) {
+        (15, 1134)
+    }
+}
+
+impl Render for Day9 {
+    // the heights, lighter the higher they are, and every basin in its own colour with 9s in black
+    fn pictures(input: &Self::Input) -> Vec<Picture> {
+        let heights = input.map(|&h| Color::gray((h * 28) as u8));
+
+        let mut basins = input.map(|_| Color::BLACK);
+        let mut visited: HashSet<Point2> = HashSet::new();
+        let mut label = 0;
+        for p in input.points() {
+            if input[p] == 9 || visited.contains(&p) {
+                continue;
+            }
+            let mut pending = vec![p];
+            visited.insert(p);
+            while let Some(current) = pending.pop() {
+                basins[current] = Color::label(label);
+                for n in input.neighbors4(current) {
+                    if input[n] != 9 && visited.insert(n) {
+                        pending.push(n);
+                    }
+                }
+            }
+            label += 1;
+        }
+
+        vec![
+            Picture::new("heights", heights),
+            Picture::new("basins", basins),
+        ]
+    }
+}
+
+pub fn format_input(input: Vec<String>) -> Result<Grid<u64>, AocError> {
+    Grid::from_digit_lines(&input)
+}
+
+impl RoundTrip for Day9 {
+    fn print(input: &Self::Input) -> Vec<String> {
+        input.to_string().lines().map(|l| l.to_string()).collect()
+    }
+
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input {
+        digit_grid(rng, size)
+    }
+
+    fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+        shrink_digit_grid(input)
+    }
+}
+
+pub fn part_1(input: Grid<u64>) -> u64 {
+    trace!("input: {:?}", input);
+    let mut risk: u64 = 0;
+
+    for p in input.points() {
+        let current = input[p];
+        // a low point is lower than all its neighbours, positions outside the grid don't count
+        let is_low_point = input.neighbors4(p).all(|n| input[n] > current);
+
+        if is_low_point {
+            risk += current + 1;
+        }
+    }
+
+    risk
+}
+
+pub fn part_2(input: Grid<u64>) -> u64 {
+    let mut basins: Vec<u64> = Vec::new();
+    let mut visited: HashSet<Point2> = HashSet::new();
+
+    // find basins
+    for p in input.points() {
+        // skip visited nodes as that means we already inspected the basin
+        if visited.contains(&p) {
+            continue;
+        }
+        let basin_size = find_basin_size(&input, p, &mut visited);
+        basins.push(basin_size);
+    }
+
+    // find top 3 and multiply them
+    basins.sort_by(|a, b| a.cmp(b).reverse());
+    basins.iter().take(3).product()
+}
+
+fn find_basin_size(input: &Grid<u64>, p: Point2, visited: &mut HashSet<Point2>) -> u64 {
+    let current_value = input[p];
+
+    // hit a border or already visited
+    if current_value == 9 || visited.contains(&p) {
+        0
+    } else {
+        visited.insert(p);
+        1 + input
+            .neighbors4(p)
+            .map(|n| find_basin_size(input, n, visited))
+            .sum::<u64>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn sample() -> <Day9 as Solution>::Input {
+        let lines = sample_data(&resources_root(None), 2021, 9).unwrap();
+        format_input(lines).unwrap()
+    }
+
+    #[test]
+    fn part_1_sample() {
+        assert_eq!(part_1(sample()), 15);
+    }
+
+    #[test]
+    fn part_2_sample() {
+        assert_eq!(part_2(sample()), 1134);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::days::y2021::{day14, day15, day6};
+use crate::error::AocError;
+use crate::generate;
+use crate::geometry::Point2;
+use crate::property::minimize;
+
+use std::collections::{BTreeMap, HashMap};
+
+// The outcome of running both implementations on an input: None when they agree, or what each of
+// them got when they don't. Inputs that don't parse are errors, and never count as a disagreement
+pub type Check = fn(Vec<String>) -> Result<Option<String>, AocError>;
+
+// Two implementations of the same thing in a day, which must always agree
+pub struct Pair {
+    pub day: u32,
+    pub name: &'static str,
+    // size given to the input generator, small enough for the slower side to be quick
+    pub size: usize,
+    pub check: Check,
+}
+
+pub const PAIRS: [Pair; 3] = [
+    Pair {
+        day: 6,
+        name: "memoised simulate vs direct simulation",
+        size: 20,
+        check: lanternfish,
+    },
+    Pair {
+        day: 14,
+        name: "step vs step_map",
+        size: 10,
+        check: polymer,
+    },
+    Pair {
+        day: 15,
+        name: "Dijkstra vs A*",
+        size: 15,
+        check: chiton,
+    },
+];
+
+fn lanternfish(input: Vec<String>) -> Result<Option<String>, AocError> {
+    let line = input.first().cloned().unwrap_or_default();
+    let fish = day6::format_input(input)?;
+    if fish.iter().any(|&f| f > 8) {
+        return Err(AocError::parse(1, 1, &line, "fish timers go up to 8"));
+    }
+    for days in [18, 80, 256] {
+        let mut memory = Default::default();
+        let memoised = fish.len() as u64
+            + fish
+                .iter()
+                .map(|&f| day6::simulate(f, 0, days, &mut memory))
+                .sum::<u64>();
+        let direct = day6::simulate_direct(&fish, days);
+        if memoised != direct {
+            return Ok(Some(format!(
+                "after {} days simulate counts {} fish and the direct simulation {}",
+                days, memoised, direct
+            )));
+        }
+    }
+    Ok(None)
+}
+
+fn polymer(input: Vec<String>) -> Result<Option<String>, AocError> {
+    let entry = day14::format_input(input)?;
+    let mut template = entry.template.clone();
+    let mut pairs = day14::count_pairs(&entry.template);
+    let insertions = day14::split_insertions(&entry.pair_insertions);
+    // sorted, so both sides print the same way
+    let sorted = |pairs: &HashMap<String, u128>| {
+        pairs
+            .iter()
+            .filter(|(_, &count)| count > 0)
+            .map(|(pair, &count)| (pair.clone(), count))
+            .collect::<BTreeMap<_, _>>()
+    };
+
+    for steps in 1..=10 {
+        template = day14::step(template, &entry.pair_insertions);
+        pairs = day14::step_map(pairs, &insertions);
+        let (expected, actual) = (sorted(&day14::count_pairs(&template)), sorted(&pairs));
+        if expected != actual {
+            return Ok(Some(format!(
+                "after {} steps step has pairs {:?} and step_map {:?}",
+                steps, expected, actual
+            )));
+        }
+    }
+    Ok(None)
+}
+
+fn chiton(input: Vec<String>) -> Result<Option<String>, AocError> {
+    let grid = day15::format_input(input)?;
+    let target = Point2::new(grid.cols() as i64 - 1, grid.rows() as i64 - 1);
+    let dijkstra = day15::find_path_lower_risk(&grid, Point2::ORIGIN, target)[target];
+    let a_star = day15::find_path_lower_risk_part_2(&grid, Point2::ORIGIN, target)[target];
+    if dijkstra != a_star {
+        return Ok(Some(format!(
+            "Dijkstra finds a risk of {} and A* {}",
+            dijkstra, a_star
+        )));
+    }
+    Ok(None)
+}
+
+// An input both implementations disagree on, shrunk as far as it would go
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    pub seed: u64,
+    pub input: Vec<String>,
+    pub message: String,
+}
+
+// Checks the pair on inputs generated from `cases` seeds starting at `seed`, stopping at the first
+// disagreement
+pub fn run(pair: &Pair, seed: u64, cases: u64, size: usize) -> Option<Disagreement> {
+    (seed..seed + cases).find_map(|seed| {
+        let input = generate::generate(pair.day, seed, size)?;
+        let message = failure(pair.check, input.clone())?;
+        let (input, message) = shrink(pair.check, input, message);
+        Some(Disagreement {
+            seed,
+            input,
+            message,
+        })
+    })
+}
+
+fn failure(check: Check, input: Vec<String>) -> Option<String> {
+    check(input).ok().flatten()
+}
+
+// Keeps taking the first smaller input that still makes the pair disagree, until none does
+pub fn shrink(check: Check, input: Vec<String>, message: String) -> (Vec<String>, String) {
+    minimize(
+        input,
+        message,
+        |input| candidates(input),
+        |candidate| failure(check, candidate.clone()).map_or(Ok(()), Err),
+    )
+}
+
+// Smaller versions of an input, the ones removing the most first. Most of them won't be valid
+// inputs for the day, which the check turns down
+fn candidates(input: &[String]) -> Vec<Vec<String>> {
+    let mut candidates = Vec::new();
+
+    // whole blocks of lines
+    let mut block = input.len() / 2;
+    while block > 0 {
+        for start in (0..input.len()).step_by(block) {
+            let mut smaller = input.to_vec();
+            smaller.drain(start..(start + block).min(input.len()));
+            candidates.push(smaller);
+        }
+        block /= 2;
+    }
+
+    // the first or last column, which keeps grids rectangular
+    if input.iter().all(|line| line.len() > 1) {
+        candidates.push(input.iter().map(|line| line[1..].to_string()).collect());
+        candidates.push(
+            input
+                .iter()
+                .map(|line| line[..line.len() - 1].to_string())
+                .collect(),
+        );
+    }
+
+    for (i, line) in input.iter().enumerate() {
+        let with_line = |line: String| {
+            let mut smaller = input.to_vec();
+            smaller[i] = line;
+            smaller
+        };
+        // single items of comma separated lists
+        let items = line.split(',').collect::<Vec<_>>();
+        if items.len() > 1 {
+            for j in 0..items.len() {
+                let mut fewer = items.clone();
+                fewer.remove(j);
+                candidates.push(with_line(fewer.join(",")));
+            }
+        }
+        // single characters, and smaller digits
+        for (j, c) in line.char_indices() {
+            let mut shorter = line.clone();
+            shorter.remove(j);
+            candidates.push(with_line(shorter));
+            if let Some(digit) = c.to_digit(10) {
+                for lower in 0..digit {
+                    let mut lowered = line.clone();
+                    lowered.replace_range(j..j + 1, &lower.to_string());
+                    candidates.push(with_line(lowered));
+                }
+            }
+        }
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs_agree() {
+        for pair in &PAIRS {
+            assert_eq!(run(pair, 0, 5, 5), None, "{}", pair.name);
+        }
+    }
+
+    // pretends to be a pair that disagrees when a list has two numbers and one of them is over 4
+    fn broken(input: Vec<String>) -> Result<Option<String>, AocError> {
+        let numbers = day6::format_input(input)?;
+        if numbers.len() > 1 && numbers.iter().any(|&n| n > 4) {
+            Ok(Some(format!("disagree on {:?}", numbers)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn shrinks_to_minimal_input() {
+        let input = vec!["3,9,1,7,2,8".to_string()];
+        let message = broken(input.clone()).unwrap().unwrap();
+        let (input, message) = shrink(broken, input, message);
+        assert_eq!(input, vec!["0,5".to_string()]);
+        assert_eq!(message, "disagree on [0, 5]");
+    }
+}
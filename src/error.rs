@@ -0,0 +1,153 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    io,
+};
+
+use crate::solution::Part;
+
+pub enum AocError {
+    MissingFile {
+        path: String,
+    },
+    // files are never overwritten by the commands that create them
+    AlreadyExists {
+        path: String,
+    },
+    Io {
+        path: String,
+        source: io::Error,
+    },
+    // line and column start at 1, as editors show them
+    Parse {
+        file: Option<String>,
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    Http {
+        url: String,
+        message: String,
+    },
+    MissingSession {
+        path: String,
+    },
+    // an answer that the submission history says can't be right
+    Refused {
+        reason: String,
+    },
+    SampleMismatch {
+        day: u32,
+        part: Part,
+        expected: String,
+        actual: String,
+    },
+    // arithmetic that overflowed while parsing the input or solving a part, when overflows are checked
+    Overflow {
+        day: u32,
+        part: Option<Part>,
+        operation: String,
+    },
+}
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, text: &str, message: impl Into<String>) -> AocError {
+        AocError::Parse {
+            file: None,
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    // parsers only see lines, so the caller that loaded them attaches the file name afterwards
+    pub fn in_file(self, name: &str) -> AocError {
+        match self {
+            AocError::Parse {
+                file: None,
+                line,
+                column,
+                text,
+                message,
+            } => AocError::Parse {
+                file: Some(name.to_string()),
+                line,
+                column,
+                text,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::MissingFile { path } => write!(f, "missing file {}", path),
+            AocError::AlreadyExists { path } => write!(f, "{} already exists", path),
+            AocError::Io { path, source } => write!(f, "could not read {}: {}", path, source),
+            AocError::Parse {
+                file,
+                line,
+                column,
+                text,
+                message,
+            } => write!(
+                f,
+                "{}:{}:{}: {} (found '{}')",
+                file.as_deref().unwrap_or("<input>"),
+                line,
+                column,
+                message,
+                text
+            ),
+            AocError::Http { url, message } => write!(f, "request to {} failed: {}", url, message),
+            AocError::MissingSession { path } => write!(
+                f,
+                "no session cookie, set {} or write it to {}",
+                crate::site::SESSION_ENV,
+                path
+            ),
+            AocError::Refused { reason } => write!(f, "not submitting: {}", reason),
+            AocError::SampleMismatch {
+                day,
+                part,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "day {} part {}: sample answer was {}, expected {}",
+                day, part, actual, expected
+            ),
+            AocError::Overflow {
+                day,
+                part: Some(part),
+                operation,
+            } => write!(f, "day {} part {} overflowed, {}", day, part, operation),
+            AocError::Overflow {
+                day,
+                part: None,
+                operation,
+            } => write!(f, "day {} overflowed parsing the input, {}", day, operation),
+        }
+    }
+}
+
+// `main` prints returned errors with Debug, so show the readable message there as well
+impl Debug for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
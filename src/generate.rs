@@ -0,0 +1,578 @@
+use std::collections::HashSet;
+
+// A small seeded generator (splitmix64), so the same seed always gives the same input
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // a number in 0..n, n must not be 0
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    // a number in low..=high
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low + 1) as u64) as i64
+    }
+
+    pub fn chance(&mut self, one_in: u64) -> bool {
+        self.below(one_in) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+// The generators write inputs for the days of this year only
+pub const YEAR: u32 = 2021;
+
+// What `--size` controls for each day, and the size used when none is given
+const SIZES: [(&str, usize); 18] = [
+    ("depth measurements", 2000),
+    ("commands", 1000),
+    ("binary numbers", 1000),
+    ("boards", 100),
+    ("segments", 500),
+    ("fish", 300),
+    ("crabs", 1000),
+    ("displays", 200),
+    ("grid width and height", 100),
+    ("lines", 100),
+    ("grid width and height", 10),
+    ("small caves", 5),
+    ("dots", 800),
+    ("template length", 20),
+    ("grid width and height", 100),
+    ("packet depth", 6),
+    ("target distance", 100),
+    ("snailfish numbers", 100),
+];
+
+pub fn size_knob(day: u32) -> Option<(&'static str, usize)> {
+    SIZES.get((day as usize).checked_sub(1)?).copied()
+}
+
+// An input for the day as the lines of the file, or None for days without a generator
+pub fn generate(day: u32, seed: u64, size: usize) -> Option<Vec<String>> {
+    let rng = &mut Rng::new(seed);
+    let size = size.max(1);
+    let input = match day {
+        1 => depths(rng, size),
+        2 => commands(rng, size),
+        3 => diagnostics(rng, size),
+        4 => bingo(rng, size),
+        5 => segments(rng, size),
+        6 => comma_list(rng, size, 1, 5),
+        7 => comma_list(rng, size, 0, 2000),
+        8 => displays(rng, size),
+        9 => digit_grid(rng, size, 0),
+        10 => chunks(rng, size),
+        11 => octopuses(rng, size),
+        12 => caves(rng, size),
+        13 => origami(rng, size),
+        14 => polymer(rng, size),
+        15 => digit_grid(rng, size, 1),
+        16 => transmission(rng, size),
+        17 => target(rng, size),
+        18 => snailfish(rng, size),
+        _ => return None,
+    };
+    Some(input)
+}
+
+fn depths(rng: &mut Rng, count: usize) -> Vec<String> {
+    let mut depth = rng.range(100, 200);
+    (0..count)
+        .map(|_| {
+            depth = (depth + rng.range(-10, 20)).max(0);
+            depth.to_string()
+        })
+        .collect()
+}
+
+fn commands(rng: &mut Rng, count: usize) -> Vec<String> {
+    // the submarine never goes above the surface
+    let mut depth = 0;
+    (0..count)
+        .map(|_| {
+            let amount = rng.range(1, 9);
+            let command = match rng.below(3) {
+                0 => "forward",
+                1 if depth >= amount => {
+                    depth -= amount;
+                    "up"
+                }
+                _ => {
+                    depth += amount;
+                    "down"
+                }
+            };
+            format!("{} {}", command, amount)
+        })
+        .collect()
+}
+
+fn diagnostics(rng: &mut Rng, count: usize) -> Vec<String> {
+    const WIDTH: usize = 12;
+    let count = count.clamp(2, 1 << WIDTH);
+    // the ratings keep filtering until one number is left, some sets run out of numbers first
+    loop {
+        let mut seen = HashSet::new();
+        let mut lines = Vec::new();
+        while lines.len() < count {
+            let n = rng.below(1 << WIDTH);
+            if seen.insert(n) {
+                lines.push(format!("{:0width$b}", n, width = WIDTH));
+            }
+        }
+        if rating_ends(&lines, true) && rating_ends(&lines, false) {
+            return lines;
+        }
+    }
+}
+
+fn rating_ends(lines: &[String], most_common: bool) -> bool {
+    let mut rest = lines.iter().map(|l| l.as_bytes()).collect::<Vec<_>>();
+    for position in 0..lines[0].len() {
+        if rest.len() == 1 {
+            break;
+        }
+        let ones = rest.iter().filter(|l| l[position] == b'1').count();
+        let zeros = rest.len() - ones;
+        let keep = match most_common {
+            true if zeros > ones => b'0',
+            true => b'1',
+            false if zeros <= ones => b'0',
+            false => b'1',
+        };
+        rest.retain(|l| l[position] == keep);
+    }
+    rest.len() == 1
+}
+
+fn bingo(rng: &mut Rng, boards: usize) -> Vec<String> {
+    // every number gets drawn, so every board wins at some point
+    let mut numbers = (0..100).collect::<Vec<_>>();
+    rng.shuffle(&mut numbers);
+    let mut input = vec![join(&numbers, ",")];
+
+    for _ in 0..boards {
+        rng.shuffle(&mut numbers);
+        input.push(String::new());
+        for row in numbers[..25].chunks(5) {
+            let row = row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>();
+            input.push(row.join(" "));
+        }
+    }
+    input
+}
+
+fn segments(rng: &mut Rng, count: usize) -> Vec<String> {
+    const SIDE: i64 = 1000;
+    (0..count)
+        .map(|_| {
+            let length = rng.range(1, 300);
+            let (dx, dy) = *rng.pick(&[(1, 0), (0, 1), (1, 1), (1, -1)]);
+            let x1 = rng.range(0, SIDE - 1 - length * dx);
+            let y1 = match dy {
+                -1 => rng.range(length, SIDE - 1),
+                _ => rng.range(0, SIDE - 1 - length * dy),
+            };
+            let (x2, y2) = (x1 + length * dx, y1 + length * dy);
+            // segments go either way
+            if rng.chance(2) {
+                format!("{},{} -> {},{}", x1, y1, x2, y2)
+            } else {
+                format!("{},{} -> {},{}", x2, y2, x1, y1)
+            }
+        })
+        .collect()
+}
+
+fn comma_list(rng: &mut Rng, count: usize, low: i64, high: i64) -> Vec<String> {
+    let values = (0..count).map(|_| rng.range(low, high)).collect::<Vec<_>>();
+    vec![join(&values, ",")]
+}
+
+fn displays(rng: &mut Rng, count: usize) -> Vec<String> {
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+    (0..count)
+        .map(|_| {
+            // each display has its wires mixed up in its own way
+            let mut wires = "abcdefg".chars().collect::<Vec<_>>();
+            rng.shuffle(&mut wires);
+            let shown = (0..4).map(|_| rng.below(10) as usize).collect::<Vec<_>>();
+            let mut encode = |digit: &str| {
+                let mut segments = digit
+                    .bytes()
+                    .map(|b| wires[(b - b'a') as usize])
+                    .collect::<Vec<_>>();
+                rng.shuffle(&mut segments);
+                segments.into_iter().collect::<String>()
+            };
+
+            let mut patterns = DIGITS.iter().map(|d| encode(d)).collect::<Vec<_>>();
+            let output = shown.iter().map(|&d| encode(DIGITS[d])).collect::<Vec<_>>();
+            rng.shuffle(&mut patterns);
+            format!("{} | {}", patterns.join(" "), output.join(" "))
+        })
+        .collect()
+}
+
+fn digit_grid(rng: &mut Rng, side: usize, lowest: u64) -> Vec<String> {
+    (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| char::from_digit((lowest + rng.below(10 - lowest)) as u32, 10).unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+fn octopuses(rng: &mut Rng, side: usize) -> Vec<String> {
+    // part 2 waits for every octopus to flash at once, which some grids never do. A grid where all of
+    // them have the same energy does, so that is used if no random grid gets there quickly
+    for _ in 0..20 {
+        let grid = digit_grid(rng, side, 0);
+        if flashes_together(&grid, 1000) {
+            return grid;
+        }
+    }
+    let energy = rng.below(10).to_string();
+    vec![energy.repeat(side); side]
+}
+
+fn flashes_together(grid: &[String], steps: usize) -> bool {
+    let side = grid.len() as i64;
+    let mut energy = grid
+        .iter()
+        .flat_map(|row| row.bytes().map(|b| b - b'0'))
+        .collect::<Vec<_>>();
+    for _ in 0..steps {
+        let mut pending = Vec::new();
+        for (i, e) in energy.iter_mut().enumerate() {
+            *e += 1;
+            if *e == 10 {
+                pending.push(i);
+            }
+        }
+        while let Some(i) = pending.pop() {
+            let (x, y) = ((i as i64) % side, (i as i64) / side);
+            for (dx, dy) in (-1..=1).flat_map(|dx| (-1..=1).map(move |dy| (dx, dy))) {
+                let (nx, ny) = (x + dx, y + dy);
+                if (dx, dy) == (0, 0) || nx < 0 || ny < 0 || nx >= side || ny >= side {
+                    continue;
+                }
+                let n = (ny * side + nx) as usize;
+                energy[n] += 1;
+                if energy[n] == 10 {
+                    pending.push(n);
+                }
+            }
+        }
+        let mut flashed = 0;
+        for e in energy.iter_mut().filter(|e| **e > 9) {
+            *e = 0;
+            flashed += 1;
+        }
+        if flashed == energy.len() {
+            return true;
+        }
+    }
+    false
+}
+
+fn chunks(rng: &mut Rng, count: usize) -> Vec<String> {
+    const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+    (0..count)
+        .map(|i| {
+            let length = rng.range(20, 100);
+            // the first line is always incomplete, so there is a middle score to find
+            let corrupted = i > 0 && rng.chance(2);
+            let mut line = String::new();
+            let mut open: Vec<(char, char)> = Vec::new();
+            for _ in 0..length {
+                if open.is_empty() || rng.chance(2) {
+                    let pair = *rng.pick(&PAIRS);
+                    line.push(pair.0);
+                    open.push(pair);
+                } else {
+                    line.push(open.pop().unwrap().1);
+                }
+            }
+            if open.is_empty() {
+                let pair = *rng.pick(&PAIRS);
+                line.push(pair.0);
+                open.push(pair);
+            }
+            if corrupted {
+                // the line stops at the first closing character that doesn't match
+                let expected = open.last().unwrap().1;
+                let wrong = PAIRS.iter().map(|p| p.1).filter(|&c| c != expected);
+                line.push(*rng.pick(&wrong.collect::<Vec<_>>()));
+            }
+            line
+        })
+        .collect()
+}
+
+fn caves(rng: &mut Rng, small: usize) -> Vec<String> {
+    let name = |rng: &mut Rng, upper: bool| {
+        (0..2)
+            .map(|_| {
+                let c = (b'a' + rng.below(26) as u8) as char;
+                if upper {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect::<String>()
+    };
+
+    let mut caves = vec!["start".to_string(), "end".to_string()];
+    let mut names = HashSet::new();
+    while names.len() < small {
+        let cave = name(rng, false);
+        if cave != "end" && names.insert(cave.clone()) {
+            caves.push(cave);
+        }
+    }
+    for _ in 0..(small / 3).max(1) {
+        let cave = name(rng, true);
+        if names.insert(cave.clone()) {
+            caves.push(cave);
+        }
+    }
+
+    // two big caves next to each other would allow paths that never end
+    let is_big = |cave: &str| cave.chars().all(|c| c.is_ascii_uppercase());
+    let mut edges = Vec::new();
+    let mut seen = HashSet::new();
+    for from in 0..caves.len() {
+        let links = if from < 2 { 2 } else { rng.range(1, 3) };
+        for _ in 0..links {
+            let to = rng.below(caves.len() as u64) as usize;
+            let (a, b) = (from.min(to), from.max(to));
+            if a == b || (is_big(&caves[a]) && is_big(&caves[b])) || !seen.insert((a, b)) {
+                continue;
+            }
+            edges.push(format!("{}-{}", caves[a], caves[b]));
+        }
+    }
+    edges
+}
+
+fn origami(rng: &mut Rng, dots: usize) -> Vec<String> {
+    // the folds leave a 40x6 sheet, each fold halving what is left, alternating x and y
+    let mut folds = Vec::new();
+    let (mut width, mut height) = (40, 6);
+    for _ in 0..4 {
+        folds.push(('y', height));
+        folds.push(('x', width));
+        width = 2 * width + 1;
+        height = 2 * height + 1;
+    }
+    folds.reverse();
+
+    // dots start on the folded sheet and are unfolded to either side of each fold line, so no dot
+    // is ever on one
+    let mut seen = HashSet::new();
+    let mut input = Vec::new();
+    for _ in 0..dots {
+        let mut x = rng.range(0, 39);
+        let mut y = rng.range(0, 5);
+        for &(axis, position) in folds.iter().rev() {
+            if rng.chance(2) {
+                match axis {
+                    'x' => x = 2 * position - x,
+                    _ => y = 2 * position - y,
+                }
+            }
+        }
+        if seen.insert((x, y)) {
+            input.push(format!("{},{}", x, y));
+        }
+    }
+    input.push(String::new());
+    for (axis, position) in folds {
+        input.push(format!("fold along {}={}", axis, position));
+    }
+    input
+}
+
+fn polymer(rng: &mut Rng, length: usize) -> Vec<String> {
+    let elements = "BCFHKNOPSV".chars().collect::<Vec<_>>();
+    let template = (0..length.max(2)).map(|_| *rng.pick(&elements));
+    let mut input = vec![template.collect::<String>(), String::new()];
+    for &a in &elements {
+        for &b in &elements {
+            input.push(format!("{}{} -> {}", a, b, rng.pick(&elements)));
+        }
+    }
+    input
+}
+
+fn transmission(rng: &mut Rng, depth: usize) -> Vec<String> {
+    let (mut bits, _) = packet(rng, depth);
+    while bits.len() % 4 != 0 {
+        bits.push('0');
+    }
+    let hex = bits
+        .as_bytes()
+        .chunks(4)
+        .map(|nibble| {
+            let value = u32::from_str_radix(std::str::from_utf8(nibble).unwrap(), 2).unwrap();
+            char::from_digit(value, 16).unwrap().to_ascii_uppercase()
+        })
+        .collect();
+    vec![hex]
+}
+
+// The bits of a packet nested `depth` levels deep, with its value. Sums and products that would get
+// too big for the solver become maximums instead
+fn packet(rng: &mut Rng, depth: usize) -> (String, u64) {
+    const LIMIT: u64 = 1 << 48;
+    let mut bits = format!("{:03b}", rng.below(8));
+
+    if depth == 0 {
+        let value = rng.below(1 << 16);
+        bits.push_str("100");
+        let digits = format!("{:b}", value);
+        let digits = format!("{:0>width$}", digits, width = digits.len().div_ceil(4) * 4);
+        let groups = digits.as_bytes().chunks(4).collect::<Vec<_>>();
+        for (i, group) in groups.iter().enumerate() {
+            bits.push(if i + 1 < groups.len() { '1' } else { '0' });
+            bits.push_str(std::str::from_utf8(group).unwrap());
+        }
+        return (bits, value);
+    }
+
+    let mut type_id = *rng.pick(&[0, 1, 2, 3, 5, 6, 7]);
+    let count = if type_id >= 5 { 2 } else { rng.range(1, 3) };
+    // the first child goes all the way down, the others stop anywhere on the way
+    let children = (0..count)
+        .map(|i| {
+            let child_depth = if i == 0 {
+                depth - 1
+            } else {
+                rng.below(depth as u64) as usize
+            };
+            packet(rng, child_depth)
+        })
+        .collect::<Vec<_>>();
+    let values = children.iter().map(|c| c.1).collect::<Vec<_>>();
+
+    let checked = |v: Option<u64>| v.filter(|&v| v < LIMIT);
+    let value = match type_id {
+        0 => checked(values.iter().try_fold(0u64, |a, &v| a.checked_add(v))),
+        1 => checked(values.iter().try_fold(1u64, |a, &v| a.checked_mul(v))),
+        2 => values.iter().min().copied(),
+        5 => Some((values[0] > values[1]) as u64),
+        6 => Some((values[0] < values[1]) as u64),
+        7 => Some((values[0] == values[1]) as u64),
+        _ => None,
+    };
+    let value = value.unwrap_or_else(|| {
+        type_id = 3;
+        *values.iter().max().unwrap()
+    });
+    bits.push_str(&format!("{:03b}", type_id));
+
+    let body = children.into_iter().map(|c| c.0).collect::<String>();
+    if body.len() < 1 << 15 && rng.chance(2) {
+        bits.push_str(&format!("0{:015b}", body.len()));
+    } else {
+        bits.push_str(&format!("1{:011b}", count));
+    }
+    bits.push_str(&body);
+    (bits, value)
+}
+
+fn target(rng: &mut Rng, distance: usize) -> Vec<String> {
+    // any target ahead and below can be hit, by a shot straight at a corner of it
+    let distance = distance.max(4) as i64;
+    let min_x = rng.range(distance / 2, distance);
+    let max_x = min_x + rng.range(1, distance / 3 + 1);
+    let min_y = -rng.range(distance / 4 + 2, distance / 2 + 2);
+    let max_y = rng.range(min_y + 1, -1);
+    vec![format!(
+        "target area: x={}..{}, y={}..{}",
+        min_x, max_x, min_y, max_y
+    )]
+}
+
+fn snailfish(rng: &mut Rng, count: usize) -> Vec<String> {
+    // numbers in the input are already reduced: nothing nested in four pairs and no number above 9
+    fn number(rng: &mut Rng, depth: usize) -> String {
+        if depth == 4 || (depth > 0 && rng.chance(3)) {
+            rng.below(10).to_string()
+        } else {
+            format!("[{},{}]", number(rng, depth + 1), number(rng, depth + 1))
+        }
+    }
+    (0..count).map(|_| number(rng, 0)).collect()
+}
+
+fn join<T: ToString>(values: &[T], separator: &str) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use crate::numeric::Policy;
+    use crate::solution::Part;
+
+    #[test]
+    fn same_seed_same_input() {
+        for day in 1..=18 {
+            assert_eq!(generate(day, 7, 10), generate(day, 7, 10));
+        }
+        assert_ne!(generate(1, 1, 10), generate(1, 2, 10));
+        assert_eq!(generate(19, 1, 10), None);
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        for day in 1..=18 {
+            let puzzle = days::get(YEAR, day).unwrap();
+            for seed in 0..3 {
+                let input = generate(day, seed, 6).unwrap();
+                for part in Part::ALL {
+                    if let Err(e) = puzzle.solve(input.clone(), part, Policy::Wrapping) {
+                        panic!("day {} seed {} part {}: {}", day, seed, part, e);
+                    }
+                }
+            }
+        }
+    }
+}
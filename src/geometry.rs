@@ -0,0 +1,306 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+// Position or offset on a plane. `x` grows to the right and `y` grows down, so on a grid `x` is the
+// column and `y` the row
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Point2 {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(self, other: Point2) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn chebyshev(self, other: Point2) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    // unit step towards the sign of each coordinate, useful to walk along lines
+    pub fn signum(self) -> Point2 {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn step(self, direction: Direction) -> Point2 {
+        self + direction.offset()
+    }
+
+    // true if the point is inside a `width` x `height` grid starting at the origin
+    pub fn within(self, width: usize, height: usize) -> bool {
+        self.x >= 0 && self.y >= 0 && (self.x as usize) < width && (self.y as usize) < height
+    }
+
+    // the four orthogonal neighbours that fall inside a `width` x `height` grid
+    pub fn neighbors4(self, width: usize, height: usize) -> impl Iterator<Item = Point2> {
+        Direction::CARDINAL
+            .into_iter()
+            .map(move |d| self.step(d))
+            .filter(move |p| p.within(width, height))
+    }
+
+    // the eight surrounding neighbours (diagonals included) that fall inside a `width` x `height` grid
+    pub fn neighbors8(self, width: usize, height: usize) -> impl Iterator<Item = Point2> {
+        Direction::ALL
+            .into_iter()
+            .map(move |d| self.step(d))
+            .filter(move |p| p.within(width, height))
+    }
+}
+
+impl Display for Point2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Add for Point2 {
+    type Output = Point2;
+
+    fn add(self, other: Point2) -> Point2 {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Point2;
+
+    fn sub(self, other: Point2) -> Point2 {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i64> for Point2 {
+    type Output = Point2;
+
+    fn mul(self, factor: i64) -> Point2 {
+        Point2::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point2 {
+    type Output = Point2;
+
+    fn neg(self) -> Point2 {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Point2 {
+    fn add_assign(&mut self, other: Point2) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point2 {
+    fn sub_assign(&mut self, other: Point2) {
+        *self = *self - other;
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn chebyshev(self, other: Point3) -> i64 {
+        (self.x - other.x)
+            .abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    pub fn signum(self) -> Point3 {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, factor: i64) -> Point3 {
+        Point3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, other: Point3) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, other: Point3) {
+        *self = *self - other;
+    }
+}
+
+// Moves on a grid, with `Up` going towards row 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    // clockwise, starting from `Up`
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::UpRight => Point2::new(1, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::DownRight => Point2::new(1, 1),
+            Direction::Down => Point2::new(0, 1),
+            Direction::DownLeft => Point2::new(-1, 1),
+            Direction::Left => Point2::new(-1, 0),
+            Direction::UpLeft => Point2::new(-1, -1),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    // turns 90 degrees clockwise
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    // turns 90 degrees counter clockwise
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    // rotates clockwise in steps of 45 degrees
+    fn rotate(self, steps: usize) -> Direction {
+        let index = Direction::ALL.iter().position(|&d| d == self).unwrap();
+        Direction::ALL[(index + steps) % Direction::ALL.len()]
+    }
+}
+
+// every point of a `width` x `height` grid, row by row
+pub fn points(width: usize, height: usize) -> impl Iterator<Item = Point2> {
+    (0..height as i64).flat_map(move |y| (0..width as i64).map(move |x| Point2::new(x, y)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(Point3::new(1, 2, 3).manhattan(Point3::ORIGIN), 6);
+        assert_eq!(Point3::new(1, -5, 3).chebyshev(Point3::ORIGIN), 5);
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let corner = Point2::ORIGIN.neighbors4(3, 2).collect::<Vec<_>>();
+        assert_eq!(corner, vec![Point2::new(1, 0), Point2::new(0, 1)]);
+        assert_eq!(Point2::new(2, 1).neighbors8(3, 2).count(), 3);
+        assert_eq!(Point2::new(1, 1).neighbors8(3, 3).count(), 8);
+        assert_eq!(Point2::new(1, 0).neighbors4(3, 1).count(), 2);
+    }
+
+    #[test]
+    fn directions_rotate() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpRight.opposite(), Direction::DownLeft);
+        assert_eq!(Point2::new(2, 2).step(Direction::UpLeft), Point2::new(1, 1));
+    }
+
+    #[test]
+    fn points_cover_grid_row_by_row() {
+        let all = points(2, 2).collect::<Vec<_>>();
+        assert_eq!(
+            all,
+            vec![
+                Point2::new(0, 0),
+                Point2::new(1, 0),
+                Point2::new(0, 1),
+                Point2::new(1, 1)
+            ]
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::error::AocError;
+use crate::geometry::Point2;
+use crate::helpers::*;
+
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+// Rectangular grid addressed by signed `(x, y)` positions, where `x` is the column and `y` the row.
+// The `grid` crate only checks upper bounds, so reads through this type return `None` for any
+// position outside the grid, negative ones included
+#[derive(Debug, Clone)]
+pub struct Grid<T> {
+    cells: ::grid::Grid<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn init(rows: usize, cols: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: ::grid::Grid::init(rows, cols, value),
+        }
+    }
+
+    // builds a grid from its cells, row by row
+    pub fn from_vec(cells: Vec<T>, cols: usize) -> Grid<T> {
+        Grid {
+            cells: ::grid::Grid::from_vec(cells, cols),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.rows()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cells.cols()
+    }
+
+    pub fn contains(&self, p: Point2) -> bool {
+        p.within(self.cols(), self.rows())
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> Option<&T> {
+        if self.contains(Point2::new(x, y)) {
+            self.cells.get(y as usize, x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: i64, y: i64) -> Option<&mut T> {
+        if self.contains(Point2::new(x, y)) {
+            self.cells.get_mut(y as usize, x as usize)
+        } else {
+            None
+        }
+    }
+
+    // every position in the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point2> {
+        crate::geometry::points(self.cols(), self.rows())
+    }
+
+    pub fn neighbors4(&self, p: Point2) -> impl Iterator<Item = Point2> {
+        p.neighbors4(self.cols(), self.rows())
+    }
+
+    pub fn neighbors8(&self, p: Point2) -> impl Iterator<Item = Point2> {
+        p.neighbors8(self.cols(), self.rows())
+    }
+
+    // every cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter_row(y)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter_col(x)
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.rows()).map(move |y| self.row(y))
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols()).map(move |x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid::from_vec(self.iter().map(f).collect(), self.cols())
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.cells.into_vec()
+    }
+}
+
+impl Grid<u64> {
+    // parses lines of digits like `2199943210`, one cell per digit
+    pub fn from_digit_lines(input: &[String]) -> Result<Grid<u64>, AocError> {
+        let columns = first_line(input)?.len();
+        if columns == 0 {
+            return Err(line_error("", 0, "expected a row of digits"));
+        }
+        let mut cells = Vec::new();
+        for (i, s) in input.iter().enumerate() {
+            if s.len() != columns {
+                return Err(line_error(s, i, "all rows must have the same length"));
+            }
+            for (c, ch) in s.char_indices() {
+                let digit = ch
+                    .to_digit(10)
+                    .ok_or_else(|| AocError::parse(i + 1, c + 1, s, "expected a digit"))?;
+                cells.push(digit as u64);
+            }
+        }
+
+        Ok(Grid::from_vec(cells, columns))
+    }
+}
+
+// indexing with a point panics outside the grid, use `get` when the position may be out of bounds
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2) -> &T {
+        self.get(p.x, p.y)
+            .unwrap_or_else(|| panic!("{} is outside the grid", p))
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, p: Point2) -> &mut T {
+        self.get_mut(p.x, p.y)
+            .unwrap_or_else(|| panic!("{} is outside the grid", p))
+    }
+}
+
+impl<T: Eq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<T: Eq> Eq for Grid<T> {}
+
+// one line per row, with the cells next to each other
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.iter_rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn get_is_bounded_on_both_sides() {
+        let grid = Grid::from_digit_lines(&lines("123\n456")).unwrap();
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(0, -1), None);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+    }
+
+    #[test]
+    fn reports_bad_digits_and_ragged_rows() {
+        let bad = Grid::from_digit_lines(&lines("123\n4x6"));
+        assert!(matches!(
+            bad,
+            Err(AocError::Parse {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+        let ragged = Grid::from_digit_lines(&lines("123\n45"));
+        assert!(matches!(ragged, Err(AocError::Parse { line: 2, .. })));
+    }
+
+    #[test]
+    fn reports_empty_grids_and_rows() {
+        for text in ["", "\n123", "123\n\n456"] {
+            assert!(matches!(
+                Grid::from_digit_lines(&lines(text)),
+                Err(AocError::Parse { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = Grid::from_digit_lines(&lines("12\n34")).unwrap();
+        assert_eq!(grid.row(1).copied().collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(grid.to_string(), "12\n34");
+    }
+}
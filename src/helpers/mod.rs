@@ -0,0 +1,206 @@
+use crate::error::AocError;
+
+use std::env;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+// environment variable pointing at the resources folder, if it isn't given on the command line
+pub const RESOURCES_ENV: &str = "AOC_RESOURCES";
+
+// Where the lines for a run come from. The default reads `<year>/input<day>.txt` from the resources folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Resources,
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    // name used when reporting errors in the input
+    pub fn name(&self, root: &Path, year: u32, day: u32) -> String {
+        match self {
+            InputSource::Resources => input_path(root, year, day).display().to_string(),
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => "<stdin>".to_string(),
+            InputSource::Inline(_) => "<inline>".to_string(),
+        }
+    }
+
+    pub fn load(&self, root: &Path, year: u32, day: u32) -> Result<Vec<String>, AocError> {
+        match self {
+            InputSource::Resources => input_data(root, year, day),
+            InputSource::File(path) => read_file(path),
+            InputSource::Stdin => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(|source| AocError::Io {
+                        path: "<stdin>".to_string(),
+                        source,
+                    })?;
+                Ok(text.lines().map(|s| s.to_string()).collect())
+            }
+            InputSource::Inline(text) => Ok(text.lines().map(|s| s.to_string()).collect()),
+        }
+    }
+}
+
+// The resources folder is taken from the command line, then the environment, then `resources` in the
+// working directory. If that doesn't exist we fall back to the one in the project, so the binary can
+// run from any directory
+pub fn resources_root(from_args: Option<&str>) -> PathBuf {
+    if let Some(root) = from_args {
+        return PathBuf::from(root);
+    }
+    if let Ok(root) = env::var(RESOURCES_ENV) {
+        return PathBuf::from(root);
+    }
+    let local = PathBuf::from("resources");
+    if local.is_dir() {
+        local
+    } else {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("resources")
+    }
+}
+
+// every year keeps its inputs, samples and answers in a folder of its own under the resources folder
+pub fn year_path(root: &Path, year: u32) -> PathBuf {
+    root.join(year.to_string())
+}
+
+pub fn input_path(root: &Path, year: u32, day: u32) -> PathBuf {
+    year_path(root, year).join(format!("input{}.txt", day))
+}
+
+pub fn sample_path(root: &Path, year: u32, day: u32) -> PathBuf {
+    year_path(root, year).join(format!("sample{}.txt", day))
+}
+
+pub fn answers_path(root: &Path, year: u32) -> PathBuf {
+    year_path(root, year).join("answers.txt")
+}
+
+// answers already found for the inputs of the year, see `cache::Cache`
+pub fn cache_path(root: &Path, year: u32) -> PathBuf {
+    year_path(root, year).join("cache.txt")
+}
+
+pub fn input_data(root: &Path, year: u32, day: u32) -> Result<Vec<String>, AocError> {
+    read_file(&input_path(root, year, day))
+}
+
+pub fn sample_data(root: &Path, year: u32, day: u32) -> Result<Vec<String>, AocError> {
+    read_file(&sample_path(root, year, day))
+}
+
+pub fn read_file(filename: &Path) -> Result<Vec<String>, AocError> {
+    let path = filename.display().to_string();
+    let io_error = |source: std::io::Error| AocError::Io {
+        path: path.clone(),
+        source,
+    };
+
+    let file = File::open(filename).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => AocError::MissingFile { path: path.clone() },
+        _ => io_error(e),
+    })?;
+    let reader = BufReader::new(file);
+
+    reader
+        .lines()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error)
+}
+
+// parses a piece of a line, reporting where it was found if it isn't valid.
+// `index` is the position of the line in the input (starting at 0) and `value` must be a slice of `line`
+pub fn parse_value<T>(line: &str, index: usize, value: &str) -> Result<T, AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = value.trim();
+    value
+        .parse::<T>()
+        .map_err(|e| AocError::parse(index + 1, column_of(line, value), value, e.to_string()))
+}
+
+// answers are kept on one line in the files they are saved to, so line breaks are written as `\n` and
+// backslashes as `\\`
+pub fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+// undoes `escape`
+pub fn unescape(text: &str) -> String {
+    let mut answer = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            answer.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => answer.push('\n'),
+            Some(escaped) => answer.push(escaped),
+            None => answer.push('\\'),
+        }
+    }
+    answer
+}
+
+// fails with a parse error pointing at the start of the line
+pub fn line_error(line: &str, index: usize, message: &str) -> AocError {
+    AocError::parse(index + 1, 1, line, message)
+}
+
+// first line of the input, for the days where everything is in a single line
+pub fn first_line(input: &[String]) -> Result<&String, AocError> {
+    input
+        .first()
+        .ok_or_else(|| line_error("", 0, "empty input"))
+}
+
+// position (starting at 1) of a slice inside the line it was taken from
+pub fn column_of(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if offset <= line.len() {
+        offset + 1
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_value_reports_position() {
+        let line = "0,9 -> 5,x";
+        let value = line.rsplit(',').next().unwrap();
+        match parse_value::<i32>(line, 2, value) {
+            Err(AocError::Parse {
+                line, column, text, ..
+            }) => assert_eq!((line, column, text.as_str()), (3, 10, "x")),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn inline_source_splits_lines() {
+        let source = InputSource::Inline("1\n2\n3".to_string());
+        let lines = source.load(Path::new("resources"), 2021, 1).unwrap();
+        assert_eq!(lines, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn missing_file_is_reported() {
+        let result = input_data(Path::new("no-such-folder"), 2021, 1);
+        assert!(matches!(result, Err(AocError::MissingFile { .. })));
+    }
+}
@@ -0,0 +1,344 @@
+use crate::error::AocError;
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+const USER_AGENT: &str = "aoc_2021 runner (https://github.com/pvillega/aoc_2021)";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+// A request with the headers every call to the site needs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub url: String,
+    pub cookie: Option<String>,
+    // `application/x-www-form-urlencoded` fields, which turn the request into a POST
+    pub form: Option<Vec<(String, String)>>,
+}
+
+impl Request {
+    pub fn get(url: &str) -> Request {
+        Request {
+            url: url.to_string(),
+            cookie: None,
+            form: None,
+        }
+    }
+
+    pub fn post(url: &str, form: Vec<(String, String)>) -> Request {
+        Request {
+            url: url.to_string(),
+            cookie: None,
+            form: Some(form),
+        }
+    }
+
+    pub fn with_cookie(mut self, cookie: &str) -> Request {
+        self.cookie = Some(cookie.to_string());
+        self
+    }
+
+    // Plain http is spoken directly over a socket, which is all a local stub server needs. For https
+    // we rely on curl rather than pulling in a TLS implementation
+    pub fn send(&self) -> Result<Response, AocError> {
+        if let Some(rest) = self.url.strip_prefix("http://") {
+            self.send_plain(rest)
+        } else if self.url.starts_with("https://") {
+            self.send_curl()
+        } else {
+            Err(self.error("only http:// and https:// urls are supported"))
+        }
+    }
+
+    fn send_plain(&self, without_scheme: &str) -> Result<Response, AocError> {
+        let (authority, path) = match without_scheme.find('/') {
+            Some(i) => without_scheme.split_at(i),
+            None => (without_scheme, "/"),
+        };
+        let address = if authority.contains(':') {
+            authority.to_string()
+        } else {
+            format!("{}:80", authority)
+        };
+
+        let mut stream = TcpStream::connect(&address).map_err(|e| self.error(&e.to_string()))?;
+        stream
+            .set_read_timeout(Some(TIMEOUT))
+            .map_err(|e| self.error(&e.to_string()))?;
+
+        let body = self.form.as_ref().map(|form| form_encode(form));
+        let mut request = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nConnection: close\r\n",
+            if body.is_some() { "POST" } else { "GET" },
+            path,
+            authority,
+            USER_AGENT
+        );
+        if let Some(cookie) = &self.cookie {
+            request.push_str(&format!("Cookie: {}\r\n", cookie));
+        }
+        if let Some(body) = &body {
+            request.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+            request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+        }
+        request.push_str("\r\n");
+        request.push_str(body.as_deref().unwrap_or(""));
+
+        stream
+            .write_all(request.as_bytes())
+            .map_err(|e| self.error(&e.to_string()))?;
+        let mut raw = Vec::new();
+        stream
+            .read_to_end(&mut raw)
+            .map_err(|e| self.error(&e.to_string()))?;
+
+        parse_response(&raw).ok_or_else(|| self.error("malformed response"))
+    }
+
+    fn send_curl(&self) -> Result<Response, AocError> {
+        // the status code goes on its own line after the body
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--location"])
+            .args(["--max-time", &TIMEOUT.as_secs().to_string()])
+            .args(["--user-agent", USER_AGENT])
+            .args(["--write-out", "\n%{http_code}"]);
+        // the session cookie is handed over on stdin, as anyone can read the arguments of a process
+        if self.cookie.is_some() {
+            command.args(["--config", "-"]);
+        }
+        if let Some(form) = &self.form {
+            command.args(["--data", &form_encode(form)]);
+        }
+        command
+            .arg(&self.url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| self.error(&format!("could not run curl: {}", e)))?;
+        if let (Some(cookie), Some(mut stdin)) = (&self.cookie, child.stdin.take()) {
+            stdin
+                .write_all(curl_config(cookie).as_bytes())
+                .map_err(|e| self.error(&format!("could not run curl: {}", e)))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| self.error(&format!("could not run curl: {}", e)))?;
+        if !output.status.success() {
+            return Err(self.error(String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = text
+            .rsplit_once('\n')
+            .ok_or_else(|| self.error("malformed response"))?;
+        let status = status
+            .trim()
+            .parse()
+            .map_err(|_| self.error("malformed response"))?;
+        Ok(Response {
+            status,
+            body: body.to_string(),
+        })
+    }
+
+    fn error(&self, message: &str) -> AocError {
+        AocError::Http {
+            url: self.url.clone(),
+            message: message.to_string(),
+        }
+    }
+}
+
+// A curl config sending the cookie, quoted so it is taken as it is. A cookie can't hold line breaks, so
+// they are dropped rather than let them start headers or options of their own
+fn curl_config(cookie: &str) -> String {
+    let quoted = cookie
+        .replace(['\n', '\r'], "")
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    format!("header = \"Cookie: {}\"\n", quoted)
+}
+
+fn parse_response(raw: &[u8]) -> Option<Response> {
+    let split = raw.windows(4).position(|w| w == b"\r\n\r\n")?;
+    let head = String::from_utf8_lossy(&raw[..split]);
+    let body = &raw[split + 4..];
+
+    let mut lines = head.lines();
+    let status = lines.next()?.split_whitespace().nth(1)?.parse().ok()?;
+    let chunked = lines.any(|l| {
+        l.to_ascii_lowercase()
+            .replace(' ', "")
+            .starts_with("transfer-encoding:chunked")
+    });
+    let body = if chunked {
+        decode_chunked(body)?
+    } else {
+        body.to_vec()
+    };
+
+    Some(Response {
+        status,
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+// bodies sent in chunks are `<hex size>\r\n<data>\r\n` repeated, ending with a chunk of size 0
+fn decode_chunked(mut raw: &[u8]) -> Option<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let line_end = raw.windows(2).position(|w| w == b"\r\n")?;
+        let size_text = String::from_utf8_lossy(&raw[..line_end]);
+        let size = usize::from_str_radix(size_text.split(';').next()?.trim(), 16).ok()?;
+        raw = &raw[line_end + 2..];
+        if size == 0 {
+            return Some(body);
+        }
+        body.extend_from_slice(raw.get(..size)?);
+        raw = raw.get(size + 2..)?;
+    }
+}
+
+pub fn form_encode(fields: &[(String, String)]) -> String {
+    fields
+        .iter()
+        .map(|(k, v)| format!("{}={}", percent_encode(k), percent_encode(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+// A server for tests that answers every connection with the given response, one per request,
+// handing back what it received
+#[cfg(test)]
+pub mod stub {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    pub struct Stub {
+        pub base_url: String,
+        pub requests: Receiver<String>,
+    }
+
+    pub fn serve(responses: Vec<(u16, &str)>) -> Stub {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let responses = responses
+            .into_iter()
+            .map(|(status, body)| (status, body.to_string()))
+            .collect::<Vec<_>>();
+        let (sender, requests) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body_in = vec![0; length];
+                reader.read_exact(&mut body_in).unwrap();
+                request.push_str(&String::from_utf8_lossy(&body_in));
+                sender.send(request).unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} OK\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        Stub { base_url, requests }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn talks_to_plain_http_server() {
+        let server = stub::serve(vec![(200, "1\n2\n"), (201, "ok")]);
+        let url = format!("{}/2021/day/1/input", server.base_url);
+
+        let response = Request::get(&url)
+            .with_cookie("session=abc")
+            .send()
+            .unwrap();
+        assert_eq!(
+            response,
+            Response {
+                status: 200,
+                body: "1\n2\n".to_string()
+            }
+        );
+        let request = server.requests.recv().unwrap();
+        assert!(request.starts_with("GET /2021/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+
+        let form = vec![("answer".to_string(), "a b&c".to_string())];
+        let response = Request::post(&url, form).send().unwrap();
+        assert_eq!(response.status, 201);
+        let request = server.requests.recv().unwrap();
+        assert!(request.starts_with("POST "));
+        assert!(request.ends_with("\r\n\r\nanswer=a%20b%26c"));
+    }
+
+    #[test]
+    fn quotes_cookie_for_curl() {
+        assert_eq!(
+            curl_config("session=abc"),
+            "header = \"Cookie: session=abc\"\n"
+        );
+        assert_eq!(
+            curl_config("a\"b\\c\nurl = x"),
+            "header = \"Cookie: a\\\"b\\\\curl = x\"\n"
+        );
+    }
+
+    #[test]
+    fn decodes_chunked_bodies() {
+        let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n2\r\nde\r\n0\r\n\r\n";
+        assert_eq!(parse_response(raw).unwrap().body, "abcde");
+    }
+}
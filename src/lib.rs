@@ -0,0 +1,30 @@
+// Solutions for Advent of Code, 2021 so far. Every day lives in its own module under `days/y<year>`,
+// exposing its `format_input`, `part_1` and `part_2` functions and the types they work with, so they can
+// be used and tested outside the `aoc` binary
+pub mod animate;
+pub mod bench;
+pub mod cache;
+pub mod days;
+pub mod differential;
+pub mod error;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod helpers;
+pub mod http;
+pub mod memory;
+pub mod numeric;
+pub mod output;
+pub mod parser;
+pub mod pool;
+pub mod property;
+pub mod render;
+pub mod roundtrip;
+pub mod runner;
+pub mod scaffold;
+pub mod site;
+pub mod solution;
+pub mod submit;
+pub mod trace;
+pub mod verify;
+pub mod watch;
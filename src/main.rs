@@ -0,0 +1,709 @@
+use aoc_2021_rust::animate;
+use aoc_2021_rust::bench::{self, Baseline, BenchOptions};
+use aoc_2021_rust::cache::Cache;
+use aoc_2021_rust::days;
+use aoc_2021_rust::differential;
+use aoc_2021_rust::generate;
+use aoc_2021_rust::helpers::*;
+use aoc_2021_rust::memory::{self, CountingAllocator};
+use aoc_2021_rust::numeric::Policy;
+use aoc_2021_rust::output::{self, Format};
+use aoc_2021_rust::pool;
+use aoc_2021_rust::render::{self, ImageFormat};
+use aoc_2021_rust::roundtrip;
+use aoc_2021_rust::runner;
+use aoc_2021_rust::scaffold;
+use aoc_2021_rust::site::{self, Fetched, Site};
+use aoc_2021_rust::solution::{Part, Puzzle};
+use aoc_2021_rust::submit::{self, Verdict};
+use aoc_2021_rust::trace::{self, Filter};
+use aoc_2021_rust::verify::{self, Answers};
+use aoc_2021_rust::watch;
+
+use std::{
+    env,
+    error::Error,
+    io,
+    path::{Path, PathBuf},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// only counts once --memory asks for it
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "usage: aoc [verify|bench] <day|all> [--part <1|2>] [--input <path|->] [--input-str <text>] [--resources <dir>]
+       [--overflow <wrapping|checked>]
+       aoc new <day> [--resources <dir>]
+       aoc fetch <day> [--base-url <url>] [--resources <dir>]
+       aoc gen <day> [--seed <n>] [--size <n>]
+       aoc diff [<day>] [--cases <n>] [--seed <n>] [--size <n>]
+       aoc roundtrip [<day>] [--cases <n>] [--seed <n>] [--size <n>]
+       aoc render <day|all> [--image <ppm|pgm|svg>] [--output <dir>]
+       aoc submit <day> --part <1|2> [--answer <value>] [--base-url <url>] [--cache]
+options for every command: [--year <year>] [--trace <level|day=level,...>]
+run options: [--format <text|json|csv>] [--jobs <n>] [--memory] [--cache] [--animate [--fps <n>]] [--watch] [--watch-exe]
+bench options: [--memory] [--iterations <n>] [--baseline <file>] [--save-baseline <file>] [--tolerance <percent>]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Run,
+    // compare the answers for the real inputs against the answers manifest
+    Verify,
+    // time parsing and both parts over several runs
+    Bench,
+    // create the files for a day that isn't solved yet
+    New(u32),
+    // download the input for a day
+    Fetch(u32),
+    // print a random input for a day
+    Gen(u32),
+    // compare the two implementations kept in some days on generated inputs
+    Diff,
+    // check that printing generated inputs and parsing them gives the same inputs back
+    RoundTrip,
+    // draw the input of the days that can be drawn into image files
+    Render,
+    // post the answer for a part, solving it first unless the answer is given
+    Submit(u32),
+}
+
+// days given on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Selection {
+    All,
+    Day(u32),
+}
+
+struct Options {
+    command: Command,
+    puzzles: Vec<&'static dyn Puzzle>,
+    parts: Vec<Part>,
+    source: InputSource,
+    resources: PathBuf,
+    format: Format,
+    // days solved at the same time when running or verifying
+    jobs: usize,
+    bench: BenchOptions,
+    year: u32,
+    base_url: Option<String>,
+    answer: Option<String>,
+    seed: Option<u64>,
+    size: Option<usize>,
+    cases: Option<u64>,
+    image: ImageFormat,
+    output: PathBuf,
+    // redraw the states a day goes through instead of solving it
+    animate: bool,
+    fps: f64,
+    // messages from the solvers shown on stderr, instead of the ones in the environment
+    trace: Option<Filter>,
+    // count allocations while running or benchmarking
+    memory: bool,
+    // run again when the input or sample changes, and with `watch_exe` when the binary is rebuilt
+    watch: bool,
+    watch_exe: bool,
+    // reuse the answers found before for the same inputs, see `cache::Cache`
+    cache: bool,
+    // whether an overflow in a solver fails the part instead of wrapping around
+    overflow: Policy,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    match &options.trace {
+        Some(filter) => trace::set_filter(filter.clone()),
+        None => trace::init_from_env()?,
+    }
+    if options.memory {
+        memory::start_counting();
+    }
+
+    match options.command {
+        Command::Run if options.animate => {
+            let puzzle = options.puzzles[0];
+            let day = puzzle.day();
+            let animation =
+                animate::get(options.year, day).ok_or(format!("day {} can't be animated", day))?;
+            let input = options.source.load(&options.resources, options.year, day)?;
+            let frames = animation.animate(input).map_err(|e| {
+                e.in_file(&options.source.name(&options.resources, options.year, day))
+            })?;
+            // like day 17 when no launch reaches the target
+            if frames.is_empty() {
+                return Err(format!("nothing to animate for day {} with this input", day).into());
+            }
+            animate::play(
+                &frames,
+                options.fps,
+                &animate::stdin_controls(),
+                &mut io::stdout().lock(),
+            )?;
+        }
+        Command::Run if options.watch => {
+            let exe = if options.watch_exe {
+                Some(env::current_exe()?)
+            } else {
+                None
+            };
+            watch::watch(
+                &options.puzzles,
+                &options.parts,
+                &options.source,
+                &options.resources,
+                options.overflow,
+                exe.as_deref(),
+            )?;
+        }
+        Command::Run => {
+            // text answers are shown as each day is done, the other formats once all days are
+            let text = options.format == Format::Text;
+            let cache = load_cache(&options)?;
+            let mut outcomes = Vec::new();
+            runner::run_days(
+                &options.puzzles,
+                &options.parts,
+                &options.source,
+                &options.resources,
+                text,
+                cache.as_ref(),
+                options.overflow,
+                options.jobs,
+                |outcome| {
+                    if text {
+                        runner::print_outcome(&outcome);
+                    } else if let Some(error) = &outcome.error {
+                        eprintln!("day {}: {}", outcome.day, error);
+                    }
+                    outcomes.push(outcome);
+                },
+            );
+            if let Some(cache) = &cache {
+                cache.save()?;
+            }
+
+            let records = outcomes
+                .iter()
+                .flat_map(|o| o.records.iter().cloned())
+                .collect::<Vec<_>>();
+            match options.format {
+                Format::Text => {}
+                Format::Json => println!("{}", output::json(&records)),
+                Format::Csv => println!("{}", output::csv(&records)),
+            }
+            if outcomes.iter().any(|o| o.is_failure()) {
+                process::exit(1);
+            }
+        }
+        Command::Verify => {
+            let answers = Answers::load(&options.resources, options.year)?;
+            let checks = verify::verify(
+                &options.puzzles,
+                &options.parts,
+                &answers,
+                &options.resources,
+                options.overflow,
+                options.jobs,
+            );
+            verify::print_table(&checks);
+            if checks.iter().any(|c| c.is_failure()) {
+                process::exit(1);
+            }
+        }
+        Command::Bench => {
+            let mut results = Vec::new();
+            for &puzzle in &options.puzzles {
+                let day = puzzle.day();
+                let input = options.source.load(&options.resources, options.year, day)?;
+                let result =
+                    bench::bench(puzzle, &input, options.bench.iterations, options.overflow)
+                        .map_err(|e| {
+                            e.in_file(&options.source.name(&options.resources, options.year, day))
+                        })?;
+                results.push(result);
+            }
+
+            let baseline = match &options.bench.baseline {
+                Some(path) => Some(Baseline::load(Path::new(path))?),
+                None => None,
+            };
+            let regressions = bench::report(&results, baseline.as_ref(), options.bench.tolerance);
+            if let Some(path) = &options.bench.save_baseline {
+                Baseline::update(Path::new(path), &results)?;
+            }
+            if regressions > 0 {
+                eprintln!(
+                    "{} phases are more than {}% slower than the baseline",
+                    regressions, options.bench.tolerance
+                );
+                process::exit(1);
+            }
+        }
+        Command::New(day) => {
+            let project = scaffold::project_root();
+            for path in scaffold::new_day(&project, &options.resources, options.year, day)? {
+                println!("wrote {}", path.display());
+            }
+        }
+        Command::Fetch(day) => {
+            let path = input_path(&options.resources, options.year, day);
+            // an input already there needs no session, so it isn't asked for
+            if site::has_input(&options.resources, options.year, day) {
+                println!("{} is already there", path.display());
+                return Ok(());
+            }
+            let site = Site::from_env(options.base_url.as_deref())?;
+            match site::fetch_input(&site, &options.resources, options.year, day)? {
+                Fetched::Downloaded => println!("downloaded {}", path.display()),
+                Fetched::Cached => println!("{} is already there", path.display()),
+            }
+        }
+        Command::Gen(day) => {
+            let (knob, default_size) =
+                generate::size_knob(day).ok_or(format!("no generator for day {}", day))?;
+            // without a seed every run is different, the seed is shown so a run can be repeated
+            let seed = options.seed.unwrap_or_else(|| {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                now.as_nanos() as u64
+            });
+            let size = options.size.unwrap_or(default_size);
+            eprintln!("day {} with seed {} and {} {}", day, seed, size, knob);
+            for line in generate::generate(day, seed, size).unwrap_or_default() {
+                println!("{}", line);
+            }
+        }
+        Command::Diff => {
+            let days = options.puzzles.iter().map(|p| p.day()).collect::<Vec<_>>();
+            let mut disagreements = 0;
+            for pair in differential::PAIRS.iter().filter(|p| days.contains(&p.day)) {
+                let size = options.size.unwrap_or(pair.size);
+                let seed = options.seed.unwrap_or(0);
+                let cases = options.cases.unwrap_or(100);
+                print!("day {} {}: ", pair.day, pair.name);
+                match differential::run(pair, seed, cases, size) {
+                    None => println!("{} inputs agree", cases),
+                    Some(disagreement) => {
+                        disagreements += 1;
+                        println!("disagree with seed {}", disagreement.seed);
+                        println!("{}", disagreement.message);
+                        println!("smallest input found:");
+                        for line in &disagreement.input {
+                            println!("{}", line);
+                        }
+                    }
+                }
+            }
+            if disagreements > 0 {
+                process::exit(1);
+            }
+        }
+        Command::RoundTrip => {
+            let cases = options.cases.unwrap_or(1000);
+            let size = options.size.unwrap_or(roundtrip::DEFAULT_SIZE);
+            let mut failures = 0;
+            for &puzzle in &options.puzzles {
+                let Some(reprint) = roundtrip::get(options.year, puzzle.day()) else {
+                    continue;
+                };
+                print!("day {}: ", puzzle.day());
+                match reprint.check(options.seed.unwrap_or(0), cases, size) {
+                    None => println!("{} inputs print and parse back", cases),
+                    Some(failure) => {
+                        failures += 1;
+                        println!("fails with seed {}", failure.seed);
+                        println!("smallest input found: {}", failure.value);
+                        println!("{}", failure.message);
+                    }
+                }
+            }
+            if failures > 0 {
+                process::exit(1);
+            }
+        }
+        Command::Render => {
+            for &puzzle in &options.puzzles {
+                let day = puzzle.day();
+                let Some(renderer) = render::get(options.year, day) else {
+                    continue;
+                };
+                let input = options.source.load(&options.resources, options.year, day)?;
+                let pictures = renderer.render(input).map_err(|e| {
+                    e.in_file(&options.source.name(&options.resources, options.year, day))
+                })?;
+                for picture in &pictures {
+                    let path = render::write(picture, day, options.image, &options.output)?;
+                    println!("wrote {}", path.display());
+                }
+            }
+        }
+        Command::Submit(day) => {
+            let part = options.parts[0];
+            let answer = match options.answer {
+                Some(answer) => answer,
+                None => solve(day, part, &options)?,
+            };
+            let site = Site::from_env(options.base_url.as_deref())?;
+            let history = submit::history_path(&options.resources);
+            println!("submitting {} for day {} part {}", answer, day, part);
+            let verdict = submit::submit(&site, &history, options.year, day, part, &answer)?;
+            println!("{}", verdict);
+            if verdict != Verdict::Right {
+                process::exit(1);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut command = Command::Run;
+    let mut selection = None;
+    let mut parts = Part::ALL.to_vec();
+    let mut part_given = false;
+    let mut source = InputSource::Resources;
+    let mut resources = None;
+    let mut format = Format::Text;
+    let mut jobs = pool::default_jobs();
+    let mut bench = BenchOptions::default();
+    let mut year = site::DEFAULT_YEAR;
+    let mut base_url = None;
+    let mut answer = None;
+    let mut seed = None;
+    let mut size = None;
+    let mut cases = None;
+    let mut image = ImageFormat::Ppm;
+    let mut output = PathBuf::from("output");
+    let mut animate = false;
+    let mut fps = animate::DEFAULT_FPS;
+    let mut trace = None;
+    let mut memory = false;
+    let mut watch = false;
+    let mut watch_exe = false;
+    let mut cache = false;
+    let mut overflow = Policy::Wrapping;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                let part = value
+                    .parse::<u32>()
+                    .ok()
+                    .and_then(Part::from_number)
+                    .ok_or(format!("invalid part: {}", value))?;
+                parts = vec![part];
+                part_given = true;
+            }
+            "--input" => {
+                let value = args.next().ok_or("--input needs a path, or - for stdin")?;
+                source = if value == "-" {
+                    InputSource::Stdin
+                } else {
+                    InputSource::File(PathBuf::from(value))
+                };
+            }
+            "--input-str" => {
+                let value = args.next().ok_or("--input-str needs the input text")?;
+                source = InputSource::Inline(value.clone());
+            }
+            "--resources" => {
+                let value = args.next().ok_or("--resources needs a directory")?;
+                resources = Some(value.as_str());
+            }
+            "--format" => {
+                let value = args.next().ok_or("--format needs text, json or csv")?;
+                format = Format::from_name(value).ok_or(format!("unknown format: {}", value))?;
+            }
+            "--jobs" => {
+                let value = args.next().ok_or("--jobs needs a number")?;
+                jobs = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or(format!("invalid number of jobs: {}", value))?;
+            }
+            "--iterations" => {
+                let value = args.next().ok_or("--iterations needs a number")?;
+                bench.iterations = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or(format!("invalid number of iterations: {}", value))?;
+            }
+            "--baseline" => {
+                let value = args.next().ok_or("--baseline needs a file")?;
+                bench.baseline = Some(value.clone());
+            }
+            "--save-baseline" => {
+                let value = args.next().ok_or("--save-baseline needs a file")?;
+                bench.save_baseline = Some(value.clone());
+            }
+            "--tolerance" => {
+                let value = args.next().ok_or("--tolerance needs a percentage")?;
+                bench.tolerance = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|t| *t >= 0.0)
+                    .ok_or(format!("invalid tolerance: {}", value))?;
+            }
+            "verify" => command = Command::Verify,
+            "bench" => command = Command::Bench,
+            "new" => command = Command::New(day_number(args.next(), "new")?),
+            "fetch" => command = Command::Fetch(day_number(args.next(), "fetch")?),
+            "gen" => command = Command::Gen(day_number(args.next(), "gen")?),
+            "--seed" => {
+                let value = args.next().ok_or("--seed needs a number")?;
+                seed = Some(
+                    value
+                        .parse::<u64>()
+                        .map_err(|_| format!("invalid seed: {}", value))?,
+                );
+            }
+            "--size" => {
+                let value = args.next().ok_or("--size needs a number")?;
+                size = Some(
+                    value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or(format!("invalid size: {}", value))?,
+                );
+            }
+            "diff" => command = Command::Diff,
+            "roundtrip" => command = Command::RoundTrip,
+            "--cases" => {
+                let value = args.next().ok_or("--cases needs a number")?;
+                cases = Some(
+                    value
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or(format!("invalid number of cases: {}", value))?,
+                );
+            }
+            "render" => command = Command::Render,
+            "--image" => {
+                let value = args.next().ok_or("--image needs ppm, pgm or svg")?;
+                image = ImageFormat::from_name(value)
+                    .ok_or(format!("unknown image format: {}", value))?;
+            }
+            "--output" => {
+                let value = args.next().ok_or("--output needs a directory")?;
+                output = PathBuf::from(value);
+            }
+            "--memory" => memory = true,
+            "--watch" => watch = true,
+            "--watch-exe" => {
+                watch = true;
+                watch_exe = true;
+            }
+            "--cache" => cache = true,
+            "--overflow" => {
+                let value = args.next().ok_or("--overflow needs wrapping or checked")?;
+                overflow = Policy::from_name(value)
+                    .ok_or(format!("unknown overflow policy: {}", value))?;
+            }
+            "--animate" => animate = true,
+            "--fps" => {
+                let value = args.next().ok_or("--fps needs a number")?;
+                fps = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|f| *f > 0.0)
+                    .ok_or(format!("invalid frame rate: {}", value))?;
+            }
+            "--trace" => {
+                let value = args
+                    .next()
+                    .ok_or("--trace needs a filter, like day16=debug")?;
+                trace = Some(Filter::parse(value)?);
+            }
+            "submit" => command = Command::Submit(day_number(args.next(), "submit")?),
+            "--answer" => {
+                let value = args.next().ok_or("--answer needs a value")?;
+                answer = Some(value.clone());
+            }
+            "--year" => {
+                let value = args.next().ok_or("--year needs a year")?;
+                year = value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|y| *y >= 2015)
+                    .ok_or(format!("invalid year: {}", value))?;
+            }
+            "--base-url" => {
+                let value = args.next().ok_or("--base-url needs a url")?;
+                base_url = Some(value.clone());
+            }
+            "all" => selection = Some(Selection::All),
+            day => {
+                let day = day
+                    .parse::<u32>()
+                    .map_err(|_| format!("unknown day: {}", day))?;
+                selection = Some(Selection::Day(day));
+            }
+        }
+    }
+
+    // the days are only known once the year is
+    let puzzles = match (selection, command) {
+        (Some(Selection::Day(day)), _) => {
+            vec![days::get(year, day).ok_or(format!("unknown day: {} of {}", day, year))?]
+        }
+        // verifying and benchmarking cover every day unless told otherwise
+        (Some(Selection::All), _)
+        | (None, Command::Verify | Command::Bench | Command::Diff | Command::RoundTrip) => {
+            if days::all(year).is_empty() {
+                let years = days::years().map(|y| y.to_string()).collect::<Vec<_>>();
+                return Err(format!(
+                    "no days solved for {}, only for {}",
+                    year,
+                    years.join(", ")
+                ));
+            }
+            days::all(year).to_vec()
+        }
+        (None, Command::New(_) | Command::Fetch(_) | Command::Gen(_) | Command::Submit(_)) => {
+            Vec::new()
+        }
+        (None, Command::Run | Command::Render) => return Err("missing day".to_string()),
+    };
+    // a custom input only makes sense for a single day
+    if puzzles.len() > 1 && source != InputSource::Resources {
+        return Err("--input and --input-str need a single day".to_string());
+    }
+    if command == Command::Verify && source != InputSource::Resources {
+        return Err("verify always uses the inputs in the resources folder".to_string());
+    }
+    // answers go one part at a time, so there is no guessing which one is meant
+    if matches!(command, Command::Submit(_)) && !part_given {
+        return Err("submit needs --part".to_string());
+    }
+    if matches!(command, Command::Gen(_) | Command::Diff) && year != generate::YEAR {
+        return Err(format!(
+            "inputs can only be generated for {}",
+            generate::YEAR
+        ));
+    }
+    if command == Command::Render && puzzles.iter().all(|p| render::get(year, p.day()).is_none()) {
+        return Err("only days 5, 9, 11, 13 and 15 can be rendered".to_string());
+    }
+    if animate && (command != Command::Run || puzzles.len() != 1) {
+        return Err("--animate runs a single day".to_string());
+    }
+    if animate && animate::get(year, puzzles[0].day()).is_none() {
+        return Err("only days 11, 13 and 17 can be animated".to_string());
+    }
+    if watch && (command != Command::Run || animate) {
+        return Err("--watch only applies when running days".to_string());
+    }
+    if watch && matches!(source, InputSource::Stdin | InputSource::Inline(_)) {
+        return Err("--watch needs the input in a file".to_string());
+    }
+    if cache && (!matches!(command, Command::Run | Command::Submit(_)) || animate || watch) {
+        return Err("--cache only applies when running days or submitting".to_string());
+    }
+    // a cached answer may have come from a run that wrapped around
+    if cache && overflow == Policy::Checked {
+        return Err("--cache can't be used with --overflow checked".to_string());
+    }
+    if overflow == Policy::Checked
+        && !matches!(
+            command,
+            Command::Run | Command::Verify | Command::Bench | Command::Submit(_)
+        )
+    {
+        return Err("--overflow only applies to the commands that solve days".to_string());
+    }
+    if memory && !matches!(command, Command::Run | Command::Bench) {
+        return Err("--memory only applies when running or benchmarking days".to_string());
+    }
+    if (command != Command::Run || animate || watch) && format != Format::Text {
+        return Err("--format only applies when running days".to_string());
+    }
+
+    Ok(Options {
+        command,
+        puzzles,
+        parts,
+        source,
+        resources: resources_root(resources),
+        format,
+        jobs,
+        bench,
+        year,
+        base_url,
+        answer,
+        seed,
+        size,
+        cases,
+        image,
+        output,
+        animate,
+        fps,
+        trace,
+        memory,
+        watch,
+        watch_exe,
+        cache,
+        overflow,
+    })
+}
+
+// day given to the commands that work on days which may not be solved yet
+fn day_number(value: Option<&String>, command: &str) -> Result<u32, String> {
+    let value = value.ok_or(format!("{} needs a day", command))?;
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|d| (1..=25).contains(d))
+        .ok_or(format!("invalid day: {}", value))
+}
+
+// the answer for a part as submitted, after checking the day still gets the sample right
+fn solve(day: u32, part: Part, options: &Options) -> Result<String, Box<dyn Error>> {
+    let puzzle = days::get(options.year, day)
+        .ok_or(format!("day {} isn't solved yet, use --answer", day))?;
+    let cache = load_cache(options)?;
+    let outcome = runner::run_day(
+        puzzle,
+        &[part],
+        &options.source,
+        &options.resources,
+        true,
+        cache.as_ref(),
+        options.overflow,
+    );
+    if let Some(error) = outcome.error {
+        return Err(error.into());
+    }
+    if let Some(cache) = &cache {
+        cache.save()?;
+    }
+    Ok(outcome.records[0].answer.clone())
+}
+
+// the cache of the year, when --cache asks for it
+fn load_cache(options: &Options) -> Result<Option<Cache>, Box<dyn Error>> {
+    if !options.cache {
+        return Ok(None);
+    }
+    Ok(Some(Cache::load(&cache_path(
+        &options.resources,
+        options.year,
+    ))?))
+}
@@ -0,0 +1,165 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+// The system allocator, counting what each thread allocates once counting is on. The binary installs it
+// as the global allocator, so nothing is counted when the library is used without it
+pub struct CountingAllocator;
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Counts {
+    allocations: u64,
+    bytes: u64,
+    // bytes allocated and not freed yet by this thread, which goes below zero when it frees memory
+    // another thread allocated
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTS: Cell<Counts> = const {
+        Cell::new(Counts {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn record(allocated: usize, freed: usize) {
+    if !COUNTING.load(Ordering::Relaxed) {
+        return;
+    }
+    // the thread local may be gone already while a thread shuts down, those allocations aren't counted
+    let _ = COUNTS.try_with(|counts| {
+        let mut c = counts.get();
+        if allocated > 0 {
+            c.allocations += 1;
+            c.bytes += allocated as u64;
+        }
+        c.live += allocated as i64 - freed as i64;
+        c.peak = c.peak.max(c.live);
+        counts.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    // counted as a new allocation of the new size replacing the old one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+pub fn start_counting() {
+    COUNTING.store(true, Ordering::Relaxed);
+}
+
+pub fn is_counting() -> bool {
+    COUNTING.load(Ordering::Relaxed)
+}
+
+// What a piece of code allocated, with the most memory it held at once on top of what was there before
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub allocations: u64,
+    pub bytes: u64,
+    pub peak: u64,
+}
+
+// Runs `f` on this thread and tells what it allocated, or None when counting is off
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    if !is_counting() {
+        return (f(), None);
+    }
+    let before = COUNTS.with(|counts| {
+        let mut c = counts.get();
+        c.peak = c.live;
+        counts.set(c);
+        c
+    });
+    let result = f();
+    let after = COUNTS.with(Cell::get);
+    let usage = Usage {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live).max(0) as u64,
+    };
+    (result, Some(usage))
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1}{}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the test binary counts too, so `measure` can be checked
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn measures_allocations_and_peak() {
+        start_counting();
+        let (sum, usage) = measure(|| {
+            let small = std::hint::black_box(vec![1u8; 1000]);
+            drop(small);
+            let big = std::hint::black_box(vec![1u8; 4000]);
+            big.iter().map(|&b| b as u64).sum::<u64>()
+        });
+        assert_eq!(sum, 4000);
+        let usage = usage.unwrap();
+        assert_eq!(
+            (usage.allocations, usage.bytes, usage.peak),
+            (2, 5000, 4000)
+        );
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0MiB");
+    }
+}
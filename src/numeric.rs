@@ -0,0 +1,254 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Display,
+};
+
+// What solvers do when arithmetic that depends on the input overflows: wrap around as release builds
+// do, or also note the operation so the part fails instead of giving a wrong answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    Wrapping,
+    Checked,
+}
+
+impl Policy {
+    pub fn from_name(name: &str) -> Option<Policy> {
+        match name {
+            "wrapping" => Some(Policy::Wrapping),
+            "checked" => Some(Policy::Checked),
+            _ => None,
+        }
+    }
+}
+
+thread_local! {
+    // the policy of the run going on in this thread, so runs in other threads may follow another one
+    static POLICY: Cell<Policy> = const { Cell::new(Policy::Wrapping) };
+    // the first operation that overflowed on this thread since the last `take_overflow`
+    static OVERFLOW: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Runs `f` with the policy on this thread, going back to the one before afterwards
+pub fn with_policy<R>(policy: Policy, f: impl FnOnce() -> R) -> R {
+    let previous = POLICY.with(|p| p.replace(policy));
+    let result = f();
+    POLICY.with(|p| p.set(previous));
+    result
+}
+
+pub fn policy() -> Policy {
+    POLICY.with(|p| p.get())
+}
+
+fn overflowed(operation: impl FnOnce() -> String) {
+    if policy() == Policy::Checked {
+        OVERFLOW.with(|o| {
+            o.borrow_mut().get_or_insert_with(operation);
+        });
+    }
+}
+
+// The operation that overflowed since the last call, if any. Solvers carry on with the wrapped value,
+// so this is looked at once a part is solved
+pub fn take_overflow() -> Option<String> {
+    OVERFLOW.with(|o| o.borrow_mut().take())
+}
+
+// The integer types solvers use, with the operations of the standard library we need
+pub trait Integer: Copy + Display {
+    const NAME: &'static str;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn wrapping_add(self, other: Self) -> Self;
+    fn wrapping_sub(self, other: Self) -> Self;
+    fn wrapping_mul(self, other: Self) -> Self;
+    // the bits of the number, and a number out of the lowest bits, which together do what `as` does
+    fn to_bits(self) -> u128;
+    fn from_bits(bits: u128) -> Self;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const NAME: &'static str = stringify!($t);
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn wrapping_add(self, other: Self) -> Self {
+                    <$t>::wrapping_add(self, other)
+                }
+
+                fn wrapping_sub(self, other: Self) -> Self {
+                    <$t>::wrapping_sub(self, other)
+                }
+
+                fn wrapping_mul(self, other: Self) -> Self {
+                    <$t>::wrapping_mul(self, other)
+                }
+
+                fn to_bits(self) -> u128 {
+                    self as u128
+                }
+
+                fn from_bits(bits: u128) -> Self {
+                    bits as $t
+                }
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// `what` says what is being computed, for the error when it overflows
+pub fn add<T: Integer>(a: T, b: T, what: &str) -> T {
+    a.checked_add(b).unwrap_or_else(|| {
+        overflowed(|| format!("{}: {} + {} overflows {}", what, a, b, T::NAME));
+        a.wrapping_add(b)
+    })
+}
+
+pub fn sub<T: Integer>(a: T, b: T, what: &str) -> T {
+    a.checked_sub(b).unwrap_or_else(|| {
+        overflowed(|| format!("{}: {} - {} overflows {}", what, a, b, T::NAME));
+        a.wrapping_sub(b)
+    })
+}
+
+pub fn mul<T: Integer>(a: T, b: T, what: &str) -> T {
+    a.checked_mul(b).unwrap_or_else(|| {
+        overflowed(|| format!("{}: {} * {} overflows {}", what, a, b, T::NAME));
+        a.wrapping_mul(b)
+    })
+}
+
+pub fn sum<T: Integer>(values: impl IntoIterator<Item = T>, what: &str) -> T {
+    values
+        .into_iter()
+        .fold(T::ZERO, |total, v| add(total, v, what))
+}
+
+pub fn product<T: Integer>(values: impl IntoIterator<Item = T>, what: &str) -> T {
+    values
+        .into_iter()
+        .fold(T::ONE, |total, v| mul(total, v, what))
+}
+
+// A number written in binary, like `10110`, keeping the lowest bits when it doesn't fit
+pub fn from_binary<T: Integer>(digits: &str, what: &str) -> T {
+    let two = T::ONE.wrapping_add(T::ONE);
+    let mut fits = true;
+    let value = digits.chars().fold(T::ZERO, |value, digit| {
+        let bit = if digit == '1' { T::ONE } else { T::ZERO };
+        match value.checked_mul(two).and_then(|v| v.checked_add(bit)) {
+            Some(v) => v,
+            None => {
+                fits = false;
+                value.wrapping_mul(two).wrapping_add(bit)
+            }
+        }
+    });
+    if !fits {
+        overflowed(|| format!("{}: {} doesn't fit in {}", what, digits, T::NAME));
+    }
+    value
+}
+
+// A number as another type, keeping the lowest bits as `as` does when it doesn't fit
+pub fn convert<T: Integer, U: Integer + TryFrom<T>>(value: T, what: &str) -> U {
+    U::try_from(value).unwrap_or_else(|_| {
+        overflowed(|| format!("{}: {} doesn't fit in {}", what, value, U::NAME));
+        U::from_bits(value.to_bits())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::y2021::day16::Day16;
+    use crate::error::AocError;
+    use crate::solution::{Part, Puzzle};
+
+    #[test]
+    fn wraps_or_reports_overflows() {
+        assert_eq!(policy(), Policy::Wrapping);
+        assert_eq!(add(u64::MAX, 2, "total"), 1);
+        assert_eq!(product([1u32 << 16, 1 << 16], "product"), 0);
+        assert_eq!(take_overflow(), None);
+
+        with_policy(Policy::Checked, || {
+            assert_eq!(sum([1u64, 2, 3], "total"), 6);
+            assert_eq!(take_overflow(), None);
+            assert_eq!(mul(i64::MAX, 2, "fish"), -2);
+            add(u32::MAX, 1, "later");
+            // only the first overflow is kept
+            assert_eq!(
+                take_overflow(),
+                Some("fish: 9223372036854775807 * 2 overflows i64".to_string())
+            );
+            assert_eq!(take_overflow(), None);
+
+            assert_eq!(convert::<u64, i32>((1 << 32) | 5, "answer"), 5);
+            assert_eq!(
+                take_overflow(),
+                Some("answer: 4294967301 doesn't fit in i32".to_string())
+            );
+        });
+        assert_eq!(policy(), Policy::Wrapping);
+        add(u32::MAX, 1, "unchecked");
+        assert_eq!(take_overflow(), None);
+    }
+
+    #[test]
+    fn reports_overflowing_day() {
+        // a literal of 72 bits, which doesn't fit in the u64 day 16 keeps values in, found while parsing
+        let mut bits = "110100".to_string();
+        for _ in 0..17 {
+            bits.push_str("11111");
+        }
+        bits.push_str("01111");
+        let hex = (0..bits.len())
+            .step_by(4)
+            .map(|i| format!("{:X}", from_binary::<u32>(&bits[i..i + 4], "digit")))
+            .collect::<String>();
+        match Day16.execute(vec![hex.clone()], &[Part::Two], Policy::Checked) {
+            Err(AocError::Overflow {
+                day: 16,
+                part: None,
+                operation,
+            }) => assert!(
+                operation.starts_with("literal value: 1111"),
+                "{}",
+                operation
+            ),
+            other => panic!("expected an overflow, got {:?}", other.map(|e| e.results)),
+        }
+        assert!(Day16
+            .execute(vec![hex], &[Part::Two], Policy::Wrapping)
+            .is_ok());
+    }
+
+    #[test]
+    fn reads_binary_numbers() {
+        assert_eq!(from_binary::<u32>("10110", "number"), 22);
+        assert_eq!(from_binary::<u32>("", "number"), 0);
+        assert_eq!(from_binary::<u8>("100000001", "number"), 1);
+    }
+}
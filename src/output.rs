@@ -0,0 +1,272 @@
+use crate::memory::{self, Usage};
+use crate::solution::Part;
+
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+// Answer for one part of a day on the selected input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    pub answer: String,
+    pub sample_passed: bool,
+    // parsing plus solving the part
+    pub elapsed: Duration,
+    // what parsing the input and then solving the part allocated, each on its own as bench shows them,
+    // when allocations are being counted
+    pub parse_memory: Option<Usage>,
+    pub memory: Option<Usage>,
+    // the answer came from the cache instead of solving the part
+    pub cached: bool,
+}
+
+impl Record {
+    fn elapsed_ms(&self) -> String {
+        format!("{:.3}", self.elapsed.as_secs_f64() * 1000.0)
+    }
+}
+
+// how answers were always shown on the terminal, with the memory of each phase on a line of its own
+pub fn text(record: &Record) -> String {
+    let cached = if record.cached { " (cached)" } else { "" };
+    let mut text = if record.answer.contains('\n') {
+        // multi line answers (like letters drawn in a grid) read better starting on their own line
+        format!("part {}{}:\n{}", record.part, cached, record.answer)
+    } else {
+        format!("part {}: {}{}", record.part, record.answer, cached)
+    };
+    for (phase, usage) in [("parse", record.parse_memory), ("solve", record.memory)] {
+        if let Some(usage) = usage {
+            text.push_str(&format!(
+                "\n  {}: {} allocations, {} allocated, {} peak",
+                phase,
+                usage.allocations,
+                memory::format_bytes(usage.bytes),
+                memory::format_bytes(usage.peak)
+            ));
+        }
+    }
+    text
+}
+
+// an array with an object per record
+pub fn json(records: &[Record]) -> String {
+    let objects = records
+        .iter()
+        .map(|r| {
+            let memory = [("parse_", r.parse_memory), ("", r.memory)]
+                .iter()
+                .filter_map(|&(prefix, usage)| {
+                    let usage = usage?;
+                    Some(format!(
+                        ", \"{p}allocations\": {}, \"{p}allocated_bytes\": {}, \"{p}peak_bytes\": {}",
+                        usage.allocations,
+                        usage.bytes,
+                        usage.peak,
+                        p = prefix
+                    ))
+                })
+                .collect::<String>();
+            let cached = if r.cached { ", \"cached\": true" } else { "" };
+            format!(
+                "  {{\"year\": {}, \"day\": {}, \"part\": {}, \"answer\": {}, \"sample_passed\": {}, \"elapsed_ms\": {}{}{}}}",
+                r.year,
+                r.day,
+                r.part,
+                json_string(&r.answer),
+                r.sample_passed,
+                r.elapsed_ms(),
+                memory,
+                cached
+            )
+        })
+        .collect::<Vec<_>>();
+    if objects.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", objects.join(",\n"))
+    }
+}
+
+// a header line and a row per record, with memory columns when allocations were counted and a cached
+// column when any answer came from the cache
+pub fn csv(records: &[Record]) -> String {
+    let counted = records
+        .iter()
+        .any(|r| r.parse_memory.is_some() || r.memory.is_some());
+    let any_cached = records.iter().any(|r| r.cached);
+    let mut header = "year,day,part,answer,sample_passed,elapsed_ms".to_string();
+    if counted {
+        header.push_str(",parse_allocations,parse_allocated_bytes,parse_peak_bytes");
+        header.push_str(",allocations,allocated_bytes,peak_bytes");
+    }
+    if any_cached {
+        header.push_str(",cached");
+    }
+    let mut lines = vec![header];
+    lines.extend(records.iter().map(|r| {
+        let mut line = format!(
+            "{},{},{},{},{},{}",
+            r.year,
+            r.day,
+            r.part,
+            csv_field(&r.answer),
+            r.sample_passed,
+            r.elapsed_ms()
+        );
+        if counted {
+            for usage in [r.parse_memory, r.memory] {
+                let usage = usage.unwrap_or_default();
+                line.push_str(&format!(
+                    ",{},{},{}",
+                    usage.allocations, usage.bytes, usage.peak
+                ));
+            }
+        }
+        if any_cached {
+            line.push_str(&format!(",{}", r.cached));
+        }
+        line
+    }));
+    lines.join("\n")
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+// fields with separators, quotes or line breaks are quoted, doubling the quotes inside
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                year: 2021,
+                day: 1,
+                part: Part::One,
+                answer: "1521".to_string(),
+                sample_passed: true,
+                elapsed: Duration::from_micros(1500),
+                parse_memory: None,
+                memory: None,
+                cached: false,
+            },
+            Record {
+                year: 2021,
+                day: 13,
+                part: Part::Two,
+                answer: "#.\n.#".to_string(),
+                sample_passed: false,
+                elapsed: Duration::from_millis(2),
+                parse_memory: None,
+                memory: None,
+                cached: false,
+            },
+        ]
+    }
+
+    #[test]
+    fn json_escapes_answers() {
+        let output = json(&records());
+        assert!(output.contains(
+            "{\"year\": 2021, \"day\": 1, \"part\": 1, \"answer\": \"1521\", \"sample_passed\": true, \"elapsed_ms\": 1.500}"
+        ));
+        assert!(output.contains("\"answer\": \"#.\\n.#\", \"sample_passed\": false"));
+        assert_eq!(json(&[]), "[]");
+    }
+
+    #[test]
+    fn csv_quotes_multi_line_answers() {
+        let output = csv(&records());
+        let lines = output.split('\n').collect::<Vec<_>>();
+        assert_eq!(lines[0], "year,day,part,answer,sample_passed,elapsed_ms");
+        assert_eq!(lines[1], "2021,1,1,1521,true,1.500");
+        assert_eq!(lines[2], "2021,13,2,\"#.");
+        assert_eq!(lines[3], ".#\",false,2.000");
+    }
+
+    #[test]
+    fn memory_columns_only_when_counted() {
+        let mut records = records();
+        records[0].parse_memory = Some(Usage {
+            allocations: 1,
+            bytes: 100,
+            peak: 100,
+        });
+        records[0].memory = Some(Usage {
+            allocations: 3,
+            bytes: 2048,
+            peak: 1024,
+        });
+        let output = csv(&records);
+        let lines = output.split('\n').collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "year,day,part,answer,sample_passed,elapsed_ms,parse_allocations,parse_allocated_bytes,parse_peak_bytes,allocations,allocated_bytes,peak_bytes"
+        );
+        assert_eq!(lines[1], "2021,1,1,1521,true,1.500,1,100,100,3,2048,1024");
+        assert!(json(&records).contains("\"elapsed_ms\": 1.500, \"parse_allocations\": 1, \"parse_allocated_bytes\": 100, \"parse_peak_bytes\": 100, \"allocations\": 3, \"allocated_bytes\": 2048, \"peak_bytes\": 1024}"));
+        assert_eq!(
+            text(&records[0]),
+            "part 1: 1521\n  parse: 1 allocations, 100B allocated, 100B peak\n  solve: 3 allocations, 2.0KiB allocated, 1.0KiB peak"
+        );
+    }
+
+    #[test]
+    fn marks_cached_answers() {
+        let mut records = records();
+        records[1].cached = true;
+        assert_eq!(text(&records[0]), "part 1: 1521");
+        assert_eq!(text(&records[1]), "part 2 (cached):\n#.\n.#");
+        assert!(json(&records).contains("\"elapsed_ms\": 2.000, \"cached\": true}"));
+        let output = csv(&records);
+        let lines = output.split('\n').collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "year,day,part,answer,sample_passed,elapsed_ms,cached"
+        );
+        assert_eq!(lines[1], "2021,1,1,1521,true,1.500,false");
+    }
+}
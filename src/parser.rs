@@ -0,0 +1,241 @@
+use crate::error::AocError;
+use crate::helpers::*;
+
+use std::{fmt::Display, str::FromStr};
+
+// A piece of an input line that remembers where it came from, so any error can point at it.
+// Parsers split spans into smaller spans until they reach the values, which are parsed with `value`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    line: &'a str,
+    index: usize,
+    text: &'a str,
+}
+
+impl<'a> Span<'a> {
+    // the whole line, `index` being its position in the input (starting at 0)
+    pub fn new(line: &'a str, index: usize) -> Span<'a> {
+        Span {
+            line,
+            index,
+            text: line,
+        }
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> AocError {
+        AocError::parse(
+            self.index + 1,
+            column_of(self.line, self.text),
+            self.text,
+            message,
+        )
+    }
+
+    pub fn value<T>(&self) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        parse_value(self.line, self.index, self.text)
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.sub(self.text.trim())
+    }
+
+    // the rest of the span after `prefix`, which must be there
+    pub fn prefix(&self, prefix: &str) -> Result<Span<'a>, AocError> {
+        self.text
+            .strip_prefix(prefix)
+            .map(|rest| self.sub(rest))
+            .ok_or_else(|| self.error(format!("expected '{}'", prefix)))
+    }
+
+    // the spans before and after the first `separator`, which must be there
+    pub fn split_once(&self, separator: &str) -> Result<(Span<'a>, Span<'a>), AocError> {
+        self.text
+            .split_once(separator)
+            .map(|(a, b)| (self.sub(a), self.sub(b)))
+            .ok_or_else(|| self.error(format!("expected '{}'", separator)))
+    }
+
+    pub fn split(&self, separator: &str) -> Vec<Span<'a>> {
+        self.text.split(separator).map(|s| self.sub(s)).collect()
+    }
+
+    pub fn words(&self) -> Vec<Span<'a>> {
+        self.text.split_whitespace().map(|s| self.sub(s)).collect()
+    }
+
+    // parses every piece between separators
+    pub fn list<T>(&self, separator: &str) -> Result<Vec<T>, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.split(separator).iter().map(|s| s.value()).collect()
+    }
+
+    fn sub(&self, text: &'a str) -> Span<'a> {
+        Span {
+            line: self.line,
+            index: self.index,
+            text,
+        }
+    }
+}
+
+// every line of the input as a span
+pub fn lines(input: &[String]) -> impl Iterator<Item = Span<'_>> {
+    input.iter().enumerate().map(|(i, line)| Span::new(line, i))
+}
+
+// integers separated by commas, like `3,4,3,1,2`
+pub fn comma_separated<T>(span: Span) -> Result<Vec<T>, AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    span.list(",")
+}
+
+// both sides of a rule like `CH -> B` or `0,9 -> 5,9`
+pub fn arrow(span: Span<'_>) -> Result<(Span<'_>, Span<'_>), AocError> {
+    span.split_once(" -> ")
+}
+
+// the value of `key=value`, where the key must be the given one
+pub fn key_value<'a>(span: Span<'a>, key: &str) -> Result<Span<'a>, AocError> {
+    let (k, value) = span.split_once("=")?;
+    if k.text() != key {
+        return Err(k.error(format!("expected '{}'", key)));
+    }
+    Ok(value)
+}
+
+// both ends of a range like `x=20..30`
+pub fn key_range<T>(span: Span<'_>, key: &str) -> Result<(T, T), AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let (from, to) = key_value(span, key)?.split_once("..")?;
+    Ok((from.value()?, to.value()?))
+}
+
+// A group of consecutive lines without blank lines between them
+#[derive(Debug, Clone, Copy)]
+pub struct Section<'a> {
+    // position of the first line of the section in the input
+    pub start: usize,
+    pub lines: &'a [String],
+}
+
+impl<'a> Section<'a> {
+    pub fn spans(&self) -> impl Iterator<Item = Span<'a>> {
+        let start = self.start;
+        self.lines
+            .iter()
+            .enumerate()
+            .map(move |(i, line)| Span::new(line, start + i))
+    }
+}
+
+// splits the input on blank lines
+pub fn sections(input: &[String]) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut start = 0;
+    for (i, line) in input.iter().enumerate() {
+        if line.trim().is_empty() {
+            if start < i {
+                sections.push(Section {
+                    start,
+                    lines: &input[start..i],
+                });
+            }
+            start = i + 1;
+        }
+    }
+    if start < input.len() {
+        sections.push(Section {
+            start,
+            lines: &input[start..],
+        });
+    }
+    sections
+}
+
+// the sections of the input, failing after its last line if there are fewer than `count`
+pub fn expect_sections<'a>(
+    input: &'a [String],
+    count: usize,
+    message: &str,
+) -> Result<Vec<Section<'a>>, AocError> {
+    let found = sections(input);
+    if found.len() < count {
+        return Err(line_error("", input.len(), message));
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(text: &str) -> Vec<String> {
+        text.lines().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn comma_separated_reports_column() {
+        assert_eq!(
+            comma_separated::<u32>(Span::new("3,4,3", 0)).unwrap(),
+            vec![3, 4, 3]
+        );
+        let error = comma_separated::<u32>(Span::new("3,x,3", 4)).unwrap_err();
+        assert!(matches!(
+            error,
+            AocError::Parse {
+                line: 5,
+                column: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn arrow_splits_rules() {
+        let (from, to) = arrow(Span::new("CH -> B", 0)).unwrap();
+        assert_eq!((from.text(), to.text()), ("CH", "B"));
+        assert!(arrow(Span::new("CH B", 0)).is_err());
+    }
+
+    #[test]
+    fn key_range_checks_key() {
+        let span = Span::new("x=-20..30", 0);
+        assert_eq!(key_range::<i64>(span, "x").unwrap(), (-20, 30));
+        let error = key_range::<i64>(span, "y").unwrap_err();
+        assert!(matches!(error, AocError::Parse { column: 1, .. }));
+    }
+
+    #[test]
+    fn sections_keep_line_positions() {
+        let lines = input("a\n\nb\nc\n\n");
+        let found = sections(&lines);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[1].start, 2);
+        let spans = found[1].spans().collect::<Vec<_>>();
+        assert!(matches!(
+            spans[1].error("bad"),
+            AocError::Parse { line: 4, .. }
+        ));
+    }
+}
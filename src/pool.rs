@@ -0,0 +1,116 @@
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+// number of workers when none is given: one per available core
+pub fn default_jobs() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+// Runs `work` on every item using `jobs` threads and hands each outcome to `emit` in the order of
+// the items, as soon as it and everything before it are done. A panic in `work` is caught and given
+// to `emit` as an error with the panic message, so the other items still run
+pub fn run_ordered<T, R>(
+    items: &[T],
+    jobs: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut emit: impl FnMut(&T, Result<R, String>),
+) where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result =
+                    panic::catch_unwind(AssertUnwindSafe(|| work(item))).map_err(panic_message);
+                if sender.send((index, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        // results arrive in any order, keep them until the ones before are emitted
+        let mut pending = BTreeMap::new();
+        let mut emitted = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&emitted) {
+                emit(&items[emitted], result);
+                emitted += 1;
+            }
+        }
+    });
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    format!("panicked: {}", message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn emits_in_item_order() {
+        let items = (0..20u64).collect::<Vec<_>>();
+        let mut seen = Vec::new();
+        run_ordered(
+            &items,
+            4,
+            |&n| {
+                // later items finish first
+                thread::sleep(Duration::from_millis(20 - n));
+                n * 2
+            },
+            |&n, result| seen.push((n, result.unwrap())),
+        );
+        assert_eq!(seen, items.iter().map(|&n| (n, n * 2)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn isolates_panics() {
+        let items = vec![1, 2, 3];
+        let mut seen = Vec::new();
+        run_ordered(
+            &items,
+            2,
+            |&n| {
+                if n == 2 {
+                    panic!("bad day {}", n);
+                }
+                n
+            },
+            |_, result| seen.push(result),
+        );
+        assert_eq!(
+            seen,
+            vec![Ok(1), Err("panicked: bad day 2".to_string()), Ok(3)]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use crate::generate::Rng;
+use crate::geometry::Point2;
+use crate::grid::Grid;
+
+// A value a property doesn't hold for, shrunk as far as it would go, with the seed it was generated from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<T> {
+    pub seed: u64,
+    pub value: T,
+    pub message: String,
+}
+
+// Checks the property on `cases` values, each generated from its own seed starting at `seed`. Sizes
+// grow up to `size` along the way, so the first values are the small ones. Stops at the first value the
+// property doesn't hold for, which is shrunk before being returned
+pub fn check<T>(
+    seed: u64,
+    cases: u64,
+    size: usize,
+    generate: impl Fn(&mut Rng, usize) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> Result<(), String>,
+) -> Option<Failure<T>> {
+    (0..cases).find_map(|case| {
+        let seed = seed.wrapping_add(case);
+        let size = 1 + (size.max(1) - 1) * (case as usize + 1) / cases as usize;
+        let value = generate(&mut Rng::new(seed), size);
+        let message = property(&value).err()?;
+        let (value, message) = minimize(value, message, &shrink, &property);
+        Some(Failure {
+            seed,
+            value,
+            message,
+        })
+    })
+}
+
+// Keeps taking the first smaller value the property still doesn't hold for, until none is left.
+// Shrinkers must only return values smaller than the one they are given, or this never ends
+pub fn minimize<T>(
+    mut value: T,
+    mut message: String,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> Result<(), String>,
+) -> (T, String) {
+    'smaller: loop {
+        for candidate in shrink(&value) {
+            if let Err(m) = property(&candidate) {
+                value = candidate;
+                message = m;
+                continue 'smaller;
+            }
+        }
+        return (value, message);
+    }
+}
+
+// A number in low..=high, landing on either end more often than chance would, as that is where
+// things tend to break
+pub fn int<T: Copy + Into<i64> + TryFrom<i64>>(rng: &mut Rng, low: T, high: T) -> T {
+    let (from, to) = (low.into(), high.into());
+    match rng.below(10) {
+        0 => low,
+        1 => high,
+        _ => {
+            let span = to.wrapping_sub(from) as u64;
+            let offset = if span == u64::MAX {
+                rng.next_u64()
+            } else {
+                rng.below(span + 1)
+            };
+            T::try_from(from.wrapping_add(offset as i64)).unwrap_or(low)
+        }
+    }
+}
+
+// Same as `int`, for numbers that may go above i64::MAX
+pub fn uint(rng: &mut Rng, low: u64, high: u64) -> u64 {
+    match rng.below(10) {
+        0 => low,
+        1 => high,
+        _ if high - low == u64::MAX => rng.next_u64(),
+        _ => low + rng.below(high - low + 1),
+    }
+}
+
+// A length in min..=max
+pub fn len(rng: &mut Rng, min: usize, max: usize) -> usize {
+    rng.range(min as i64, max.max(min) as i64) as usize
+}
+
+pub fn vec_of<T>(
+    rng: &mut Rng,
+    min: usize,
+    max: usize,
+    mut item: impl FnMut(&mut Rng) -> T,
+) -> Vec<T> {
+    let count = len(rng, min, max);
+    (0..count).map(|_| item(rng)).collect()
+}
+
+// A word made of the characters in `alphabet`
+pub fn word(rng: &mut Rng, alphabet: &str, min: usize, max: usize) -> String {
+    let chars = alphabet.chars().collect::<Vec<_>>();
+    vec_of(rng, min, max, |rng| *rng.pick(&chars))
+        .into_iter()
+        .collect()
+}
+
+// A grid of single digits, like the inputs of days 9, 11 and 15
+pub fn digit_grid(rng: &mut Rng, size: usize) -> Grid<u64> {
+    let (rows, cols) = (len(rng, 1, size), len(rng, 1, size));
+    let cells = (0..rows * cols).map(|_| rng.below(10)).collect();
+    Grid::from_vec(cells, cols)
+}
+
+// Numbers closer to 0, the closest first
+pub fn shrink_int<T: Copy + Into<i64> + TryFrom<i64>>(n: T) -> Vec<T> {
+    let n = n.into();
+    let mut smaller = vec![0, n / 2, n - n.signum()];
+    smaller.dedup();
+    smaller.retain(|&m| m != n);
+    smaller
+        .into_iter()
+        .filter_map(|m| T::try_from(m).ok())
+        .collect()
+}
+
+pub fn shrink_uint(n: u64) -> Vec<u64> {
+    let mut smaller = vec![0, n / 2, n.saturating_sub(1)];
+    smaller.dedup();
+    smaller.retain(|&m| m != n);
+    smaller
+}
+
+// Points closer to the origin, one coordinate at a time
+pub fn shrink_point(p: Point2) -> Vec<Point2> {
+    let xs = shrink_int(p.x).into_iter().map(|x| Point2::new(x, p.y));
+    let ys = shrink_int(p.y).into_iter().map(|y| Point2::new(p.x, y));
+    xs.chain(ys).collect()
+}
+
+// Lists with fewer items, the ones removing the most first but never going below `min` items, then the
+// same list with one of its items shrunk
+pub fn shrink_vec<T: Clone>(items: &[T], min: usize, item: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut smaller = Vec::new();
+    let mut block = items.len() / 2;
+    while block > 0 {
+        for start in (0..items.len()).step_by(block) {
+            let end = (start + block).min(items.len());
+            if items.len() - (end - start) >= min {
+                let mut fewer = items.to_vec();
+                fewer.drain(start..end);
+                smaller.push(fewer);
+            }
+        }
+        block /= 2;
+    }
+    for (i, value) in items.iter().enumerate() {
+        for shrunk in item(value) {
+            let mut changed = items.to_vec();
+            changed[i] = shrunk;
+            smaller.push(changed);
+        }
+    }
+    smaller
+}
+
+// Grids without their last row or column, then with one cell lowered
+pub fn shrink_digit_grid(grid: &Grid<u64>) -> Vec<Grid<u64>> {
+    let (rows, cols) = (grid.rows(), grid.cols());
+    let cells = grid.iter().copied().collect::<Vec<_>>();
+    let mut smaller = Vec::new();
+    if rows > 1 {
+        smaller.push(Grid::from_vec(cells[..(rows - 1) * cols].to_vec(), cols));
+    }
+    if cols > 1 {
+        let fewer = cells
+            .chunks(cols)
+            .flat_map(|row| row[..cols - 1].to_vec())
+            .collect();
+        smaller.push(Grid::from_vec(fewer, cols - 1));
+    }
+    for (i, &cell) in cells.iter().enumerate() {
+        for lower in shrink_uint(cell) {
+            let mut changed = cells.clone();
+            changed[i] = lower;
+            smaller.push(Grid::from_vec(changed, cols));
+        }
+    }
+    smaller
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks_to_smallest_failure() {
+        let failure = check(
+            0,
+            100,
+            20,
+            |rng, size| vec_of(rng, 0, size, |rng| int(rng, 0i64, 1000)),
+            |numbers| shrink_vec(numbers, 0, |&n| shrink_int(n)),
+            |numbers| match numbers.iter().find(|&&n| n >= 500) {
+                Some(n) => Err(format!("{} is too big", n)),
+                None => Ok(()),
+            },
+        )
+        .unwrap();
+        assert_eq!(failure.value, vec![500]);
+        assert_eq!(failure.message, "500 is too big");
+        assert_eq!(
+            check(0, 100, 20, int_of_size, |_| Vec::new(), |_| Ok(())),
+            None
+        );
+    }
+
+    fn int_of_size(rng: &mut Rng, size: usize) -> i64 {
+        int(rng, 0, size as i64)
+    }
+
+    #[test]
+    fn generates_within_bounds() {
+        let rng = &mut Rng::new(7);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&int(rng, -3, 3)));
+            assert!((2..=5).contains(&len(rng, 2, 5)));
+        }
+        // the whole range of the type can be asked for
+        int(rng, i64::MIN, i64::MAX);
+        uint(rng, 0, u64::MAX);
+        assert_eq!(shrink_int(-7), vec![0, -3, -6]);
+        assert_eq!(shrink_int(i32::MIN), vec![0, i32::MIN / 2, i32::MIN + 1]);
+        assert_eq!(shrink_uint(1), vec![0]);
+        assert!(shrink_int(0).is_empty());
+
+        let grid = digit_grid(rng, 4);
+        assert!(shrink_digit_grid(&grid)
+            .iter()
+            .all(|g| g.rows() * g.cols() <= grid.rows() * grid.cols()));
+    }
+}
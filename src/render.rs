@@ -0,0 +1,314 @@
+use crate::days::y2021::{day11::Day11, day13::Day13, day15::Day15, day5::Day5, day9::Day9};
+use crate::error::AocError;
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    // what paths are drawn with, over everything else
+    pub const PATH: Color = Color::rgb(230, 30, 40);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+
+    pub fn gray(level: u8) -> Color {
+        Color::rgb(level, level, level)
+    }
+
+    // a value in 0..=max, from dark blue through red to pale yellow
+    pub fn heat(value: u64, max: u64) -> Color {
+        const STOPS: [Color; 4] = [
+            Color::rgb(10, 10, 60),
+            Color::rgb(120, 20, 140),
+            Color::rgb(230, 60, 40),
+            Color::rgb(255, 240, 150),
+        ];
+        let t = value.min(max) as f64 / max.max(1) as f64 * (STOPS.len() - 1) as f64;
+        let i = (t as usize).min(STOPS.len() - 2);
+        let f = t - i as f64;
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * f).round() as u8;
+        let (a, b) = (STOPS[i], STOPS[i + 1]);
+        Color::rgb(mix(a.r, b.r), mix(a.g, b.g), mix(a.b, b.b))
+    }
+
+    // colours for numbered regions, with neighbouring numbers far apart in hue
+    pub fn label(n: usize) -> Color {
+        let hue = (n as f64 * 137.508) % 360.0;
+        let x = 1.0 - ((hue / 60.0) % 2.0 - 1.0).abs();
+        let (r, g, b) = match (hue / 60.0) as u32 {
+            0 => (1.0, x, 0.0),
+            1 => (x, 1.0, 0.0),
+            2 => (0.0, 1.0, x),
+            3 => (0.0, x, 1.0),
+            4 => (x, 0.0, 1.0),
+            _ => (1.0, 0.0, x),
+        };
+        let channel = |c: f64| (60.0 + c * 170.0) as u8;
+        Color::rgb(channel(r), channel(g), channel(b))
+    }
+
+    fn luminance(self) -> u8 {
+        ((299 * self.r as u32 + 587 * self.g as u32 + 114 * self.b as u32) / 1000) as u8
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+// A grid of coloured cells, with an optional path drawn over them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    pub name: String,
+    pub cells: Grid<Color>,
+    pub path: Vec<Point2>,
+}
+
+impl Picture {
+    pub fn new(name: &str, cells: Grid<Color>) -> Picture {
+        Picture {
+            name: name.to_string(),
+            cells,
+            path: Vec::new(),
+        }
+    }
+
+    pub fn with_path(mut self, path: Vec<Point2>) -> Picture {
+        self.path = path;
+        self
+    }
+
+    // pixels per cell, so small grids don't end up as a few pixels
+    pub fn scale(&self) -> usize {
+        (400 / self.cells.rows().max(self.cells.cols()).max(1)).max(1)
+    }
+
+    // the cells with the path painted on them, for formats that only have pixels
+    fn flattened(&self) -> Grid<Color> {
+        let mut cells = self.cells.clone();
+        for &p in &self.path {
+            if cells.contains(p) {
+                cells[p] = Color::PATH;
+            }
+        }
+        cells
+    }
+}
+
+// Days that can draw their input, next to solving it
+pub trait Render: Solution {
+    fn pictures(input: &Self::Input) -> Vec<Picture>;
+}
+
+// The object safe view of Render, for the registry below
+pub trait Renderer: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn render(&self, input: Vec<String>) -> Result<Vec<Picture>, AocError>;
+}
+
+impl<R: Render + Sync> Renderer for R {
+    fn year(&self) -> u32 {
+        R::YEAR
+    }
+
+    fn day(&self) -> u32 {
+        R::DAY
+    }
+
+    fn render(&self, input: Vec<String>) -> Result<Vec<Picture>, AocError> {
+        Ok(R::pictures(&R::parse(input)?))
+    }
+}
+
+static RENDERERS: [&dyn Renderer; 5] = [&Day5, &Day9, &Day11, &Day13, &Day15];
+
+pub fn all() -> &'static [&'static dyn Renderer] {
+    &RENDERERS
+}
+
+pub fn get(year: u32, day: u32) -> Option<&'static dyn Renderer> {
+    RENDERERS
+        .iter()
+        .copied()
+        .find(|r| r.year() == year && r.day() == day)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    // binary netpbm colour and greyscale images
+    Ppm,
+    Pgm,
+    Svg,
+}
+
+impl ImageFormat {
+    pub fn from_name(name: &str) -> Option<ImageFormat> {
+        match name {
+            "ppm" => Some(ImageFormat::Ppm),
+            "pgm" => Some(ImageFormat::Pgm),
+            "svg" => Some(ImageFormat::Svg),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Pgm => "pgm",
+            ImageFormat::Svg => "svg",
+        }
+    }
+}
+
+pub fn encode(picture: &Picture, format: ImageFormat) -> Vec<u8> {
+    match format {
+        ImageFormat::Ppm => netpbm(picture, "P6", |c| vec![c.r, c.g, c.b]),
+        ImageFormat::Pgm => netpbm(picture, "P5", |c| vec![c.luminance()]),
+        ImageFormat::Svg => svg(picture).into_bytes(),
+    }
+}
+
+fn netpbm(picture: &Picture, magic: &str, pixel: fn(Color) -> Vec<u8>) -> Vec<u8> {
+    let cells = picture.flattened();
+    let scale = picture.scale();
+    let (width, height) = (cells.cols() * scale, cells.rows() * scale);
+    let mut bytes = format!("{}\n{} {}\n255\n", magic, width, height).into_bytes();
+    for row in cells.iter_rows() {
+        let line = row
+            .flat_map(|&c| (0..scale).flat_map(move |_| pixel(c)))
+            .collect::<Vec<_>>();
+        for _ in 0..scale {
+            bytes.extend_from_slice(&line);
+        }
+    }
+    bytes
+}
+
+// One rectangle per run of same coloured cells in a row, in cell units, and the path as a line through
+// the middle of its cells
+fn svg(picture: &Picture) -> String {
+    let cells = &picture.cells;
+    let scale = picture.scale();
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+        cells.cols() * scale,
+        cells.rows() * scale,
+        cells.cols(),
+        cells.rows()
+    );
+    for (y, row) in cells.iter_rows().enumerate() {
+        let row = row.collect::<Vec<_>>();
+        let mut x = 0;
+        while x < row.len() {
+            let run = row[x..].iter().take_while(|&&c| c == row[x]).count();
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"{}\"/>\n",
+                x,
+                y,
+                run,
+                row[x].hex()
+            ));
+            x += run;
+        }
+    }
+    if !picture.path.is_empty() {
+        let points = picture
+            .path
+            .iter()
+            .map(|p| format!("{}.5,{}.5", p.x, p.y))
+            .collect::<Vec<_>>();
+        svg.push_str(&format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.3\"/>\n",
+            points.join(" "),
+            Color::PATH.hex()
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Writes the picture as `day<N>-<name>.<extension>` in the directory, creating it if needed
+pub fn write(
+    picture: &Picture,
+    day: u32,
+    format: ImageFormat,
+    dir: &Path,
+) -> Result<PathBuf, AocError> {
+    let path = dir.join(format!(
+        "day{}-{}.{}",
+        day,
+        picture.name,
+        format.extension()
+    ));
+    let io_error = |source| AocError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+    fs::create_dir_all(dir).map_err(io_error)?;
+    fs::write(&path, encode(picture, format)).map_err(io_error)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{resources_root, sample_data};
+
+    fn picture() -> Picture {
+        let cells = Grid::from_vec(vec![Color::WHITE, Color::WHITE, Color::BLACK], 3);
+        Picture::new("test", cells).with_path(vec![Point2::new(2, 0)])
+    }
+
+    #[test]
+    fn writes_netpbm() {
+        let picture = picture();
+        assert_eq!(picture.scale(), 133);
+        let ppm = encode(&picture, ImageFormat::Ppm);
+        let header = b"P6\n399 133\n255\n";
+        assert!(ppm.starts_with(header));
+        assert_eq!(ppm.len(), header.len() + 399 * 133 * 3);
+        // the path is painted over the last cell
+        assert_eq!(&ppm[ppm.len() - 3..], &[230, 30, 40]);
+
+        let pgm = encode(&picture, ImageFormat::Pgm);
+        assert_eq!(pgm.len(), b"P5\n399 133\n255\n".len() + 399 * 133);
+        assert_eq!(pgm[pgm.len() - 399], 255);
+    }
+
+    #[test]
+    fn writes_svg_runs_and_path() {
+        let svg = String::from_utf8(encode(&picture(), ImageFormat::Svg)).unwrap();
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"2\" height=\"1\" fill=\"#ffffff\"/>"));
+        assert!(svg.contains("<rect x=\"2\" y=\"0\" width=\"1\" height=\"1\" fill=\"#000000\"/>"));
+        assert!(svg.contains("<polyline points=\"2.5,0.5\""));
+    }
+
+    #[test]
+    fn renders_samples() {
+        let root = resources_root(None);
+        for renderer in all() {
+            let sample = sample_data(&root, renderer.year(), renderer.day()).unwrap();
+            let pictures = renderer.render(sample).unwrap();
+            assert!(!pictures.is_empty());
+            assert!(pictures
+                .iter()
+                .all(|p| p.cells.rows() > 0 && p.cells.cols() > 0));
+        }
+    }
+}
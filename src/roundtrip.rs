@@ -0,0 +1,106 @@
+use crate::days::y2021::{
+    day1::Day1, day10::Day10, day11::Day11, day12::Day12, day13::Day13, day14::Day14, day15::Day15,
+    day16::Day16, day17::Day17, day18::Day18, day2::Day2, day3::Day3, day4::Day4, day5::Day5,
+    day6::Day6, day7::Day7, day8::Day8, day9::Day9,
+};
+use crate::generate::Rng;
+use crate::property::{self, Failure};
+use crate::solution::Solution;
+
+use std::fmt::Debug;
+
+// Values generated when no size is given: items in a list, rows and columns of a grid...
+pub const DEFAULT_SIZE: usize = 12;
+
+// Days whose parsed input can be written back as puzzle text, with a way to make up inputs and to make
+// them smaller, so parsing what was printed can be checked to give the same input back
+pub trait RoundTrip: Solution {
+    // the input as the lines of a puzzle file
+    fn print(input: &Self::Input) -> Vec<String>;
+    // a random input of about `size` items
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self::Input;
+    // smaller versions of the input, to narrow down a failure
+    fn shrink(input: &Self::Input) -> Vec<Self::Input>;
+}
+
+// The object safe view of RoundTrip, for the registry below
+pub trait Reprint: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    // the smallest input found that doesn't survive printing and parsing, shown with Debug
+    fn check(&self, seed: u64, cases: u64, size: usize) -> Option<Failure<String>>;
+}
+
+impl<R: RoundTrip + Sync> Reprint for R
+where
+    R::Input: PartialEq + Debug,
+{
+    fn year(&self) -> u32 {
+        R::YEAR
+    }
+
+    fn day(&self) -> u32 {
+        R::DAY
+    }
+
+    fn check(&self, seed: u64, cases: u64, size: usize) -> Option<Failure<String>> {
+        let failure = property::check(seed, cases, size, R::arbitrary, R::shrink, round_trip::<R>)?;
+        Some(Failure {
+            seed: failure.seed,
+            value: format!("{:?}", failure.value),
+            message: failure.message,
+        })
+    }
+}
+
+fn round_trip<R: RoundTrip>(input: &R::Input) -> Result<(), String>
+where
+    R::Input: PartialEq + Debug,
+{
+    let text = R::print(input);
+    match R::parse(text.clone()) {
+        Ok(parsed) if parsed == *input => Ok(()),
+        Ok(parsed) => Err(format!(
+            "printed as:\n{}\nwhich parses as {:?}",
+            text.join("\n"),
+            parsed
+        )),
+        Err(e) => Err(format!(
+            "printed as:\n{}\nwhich doesn't parse: {}",
+            text.join("\n"),
+            e
+        )),
+    }
+}
+
+static ROUND_TRIPS: [&dyn Reprint; 18] = [
+    &Day1, &Day2, &Day3, &Day4, &Day5, &Day6, &Day7, &Day8, &Day9, &Day10, &Day11, &Day12, &Day13,
+    &Day14, &Day15, &Day16, &Day17, &Day18,
+];
+
+pub fn get(year: u32, day: u32) -> Option<&'static dyn Reprint> {
+    ROUND_TRIPS
+        .iter()
+        .copied()
+        .find(|r| r.year() == year && r.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_day_round_trips() {
+        for reprint in ROUND_TRIPS.iter() {
+            if let Some(failure) = reprint.check(0, 2000, DEFAULT_SIZE) {
+                panic!(
+                    "day {} with seed {}: {}\n{}",
+                    reprint.day(),
+                    failure.seed,
+                    failure.value,
+                    failure.message
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::cache::{self, Cache};
+use crate::error::AocError;
+use crate::helpers::*;
+use crate::numeric::Policy;
+use crate::output::{self, Record};
+use crate::pool;
+use crate::solution::{Part, Puzzle};
+
+use std::{path::Path, process::ExitCode, time::Duration};
+
+// What running a day produced: the answers it got to, and the error that stopped it, if any
+#[derive(Debug, Clone)]
+pub struct DayOutcome {
+    pub day: u32,
+    pub records: Vec<Record>,
+    pub error: Option<String>,
+}
+
+impl DayOutcome {
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some() || self.records.iter().any(|r| !r.sample_passed)
+    }
+}
+
+// Solves the given parts, checking the sample first. When `strict` a wrong sample answer stops the day
+// with an error, otherwise the sample status is part of each record. With a cache, parts already solved
+// for the same input and solver version aren't solved again, and the new answers that got the sample
+// right are added to it. `overflow` is the policy for arithmetic that overflows in the solver
+pub fn run_day(
+    puzzle: &dyn Puzzle,
+    parts: &[Part],
+    source: &InputSource,
+    root: &Path,
+    strict: bool,
+    cache: Option<&Cache>,
+    overflow: Policy,
+) -> DayOutcome {
+    let mut records = Vec::new();
+    let error = solve_parts(
+        puzzle,
+        parts,
+        source,
+        root,
+        strict,
+        cache,
+        overflow,
+        &mut records,
+    )
+    .err();
+    DayOutcome {
+        day: puzzle.day(),
+        records,
+        error: error.map(|e| e.to_string()),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn solve_parts(
+    puzzle: &dyn Puzzle,
+    parts: &[Part],
+    source: &InputSource,
+    root: &Path,
+    strict: bool,
+    cache: Option<&Cache>,
+    overflow: Policy,
+    records: &mut Vec<Record>,
+) -> Result<(), AocError> {
+    let (year, day) = (puzzle.year(), puzzle.day());
+    let sample = sample_data(root, year, day)?;
+    let input = source.load(root, year, day)?;
+    let hash = cache.map(|_| cache::input_hash(&input)).unwrap_or_default();
+
+    for &part in parts {
+        // only answers that got the sample right are cached, so there is nothing to check again
+        if let Some(answer) = cache.and_then(|c| c.get(day, part, puzzle.version(), hash)) {
+            records.push(Record {
+                year,
+                day,
+                part,
+                answer,
+                sample_passed: true,
+                elapsed: Duration::ZERO,
+                parse_memory: None,
+                memory: None,
+                cached: true,
+            });
+            continue;
+        }
+
+        // check the sample before trusting the answer for the real input
+        let sample_result = puzzle
+            .solve(sample.clone(), part, overflow)
+            .map_err(|e| e.in_file(&sample_path(root, year, day).display().to_string()))?;
+        let expected = puzzle.sample_answer(part);
+        if sample_result != expected && strict {
+            return Err(AocError::SampleMismatch {
+                day,
+                part,
+                expected,
+                actual: sample_result,
+            });
+        }
+
+        let execution = puzzle
+            .execute(input.clone(), &[part], overflow)
+            .map_err(|e| e.in_file(&source.name(root, year, day)))?;
+        let result = &execution.results[0];
+        if let (Some(cache), true) = (cache, sample_result == expected) {
+            cache.put(day, part, puzzle.version(), hash, &result.answer);
+        }
+        records.push(Record {
+            year,
+            day,
+            part,
+            answer: result.answer.clone(),
+            sample_passed: sample_result == expected,
+            elapsed: execution.parse_time + result.time,
+            parse_memory: execution.parse_memory,
+            memory: result.memory,
+            cached: false,
+        });
+    }
+
+    Ok(())
+}
+
+// Runs the days on `jobs` threads, handing the outcomes to `emit` in day order. A day that panics is
+// reported as an error without stopping the others
+#[allow(clippy::too_many_arguments)]
+pub fn run_days(
+    puzzles: &[&dyn Puzzle],
+    parts: &[Part],
+    source: &InputSource,
+    root: &Path,
+    strict: bool,
+    cache: Option<&Cache>,
+    overflow: Policy,
+    jobs: usize,
+    mut emit: impl FnMut(DayOutcome),
+) {
+    pool::run_ordered(
+        puzzles,
+        jobs,
+        |&puzzle| run_day(puzzle, parts, source, root, strict, cache, overflow),
+        |puzzle, result| {
+            emit(result.unwrap_or_else(|message| DayOutcome {
+                day: puzzle.day(),
+                records: Vec::new(),
+                error: Some(message),
+            }))
+        },
+    );
+}
+
+// The day's answers as text, and the error that stopped it on stderr
+pub fn print_outcome(outcome: &DayOutcome) {
+    println!("day {}", outcome.day);
+    for record in &outcome.records {
+        println!("{}", output::text(record));
+    }
+    if let Some(error) = &outcome.error {
+        eprintln!("Error: {}", error);
+    }
+}
+
+// What the per-day binaries (`cargo run --bin day15`) run: both parts of the day on its input in the
+// resources folder, printed the way `aoc 15` does
+pub fn run_alone(puzzle: &dyn Puzzle) -> ExitCode {
+    let root = resources_root(None);
+    let outcome = run_day(
+        puzzle,
+        &Part::ALL,
+        &InputSource::Resources,
+        &root,
+        true,
+        None,
+        Policy::Wrapping,
+    );
+    print_outcome(&outcome);
+    if outcome.is_failure() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,462 @@
+use crate::error::AocError;
+use crate::helpers::*;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs.template");
+// the registry of a year without days yet
+const YEAR_TEMPLATE: &str = include_str!("../templates/year.rs.template");
+
+// The project the new day is added to: the working directory when it is one, so scaffolding works on a
+// checkout other than the one `aoc` was built from, and this one otherwise
+pub fn project_root() -> PathBuf {
+    let local = PathBuf::from(".");
+    if local.join("src/days/mod.rs").is_file() {
+        local
+    } else {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    }
+}
+
+pub fn day_path(project: &Path, year: u32, day: u32) -> PathBuf {
+    year_module_path(project, year).join(format!("day{}.rs", day))
+}
+
+fn year_module_path(project: &Path, year: u32) -> PathBuf {
+    project.join("src/days").join(format!("y{}", year))
+}
+
+fn years_registry_path(project: &Path) -> PathBuf {
+    project.join("src/days/mod.rs")
+}
+
+// Creates the module for a new day, empty sample and input files, and registers the day, starting the
+// registry of the year if it is its first day. Nothing is written if any of the files already exists
+// or the day is already registered. Returns the files created or changed
+pub fn new_day(
+    project: &Path,
+    resources: &Path,
+    year: u32,
+    day: u32,
+) -> Result<Vec<PathBuf>, AocError> {
+    let module = day_path(project, year, day);
+    let registry = year_module_path(project, year).join("mod.rs");
+    let new_files = [
+        module.clone(),
+        sample_path(resources, year, day),
+        input_path(resources, year, day),
+    ];
+    if let Some(existing) = new_files.iter().find(|p| p.exists()) {
+        return Err(AocError::AlreadyExists {
+            path: existing.display().to_string(),
+        });
+    }
+
+    let years_registry = years_registry_path(project);
+    let (days, years) = if registry.exists() {
+        (read_file(&registry)?.join("\n"), None)
+    } else {
+        let years =
+            register_year(&read_file(&years_registry)?.join("\n"), year).ok_or_else(|| {
+                AocError::AlreadyExists {
+                    path: format!("year {} in {}", year, years_registry.display()),
+                }
+            })?;
+        (YEAR_TEMPLATE.to_string(), Some(years))
+    };
+    let registered = register(&days, day).ok_or_else(|| AocError::AlreadyExists {
+        path: format!("day {} in {}", day, registry.display()),
+    })?;
+
+    create_dir(&year_module_path(project, year))?;
+    create_dir(&year_path(resources, year))?;
+    write(
+        &module,
+        &TEMPLATE
+            .replace("{{year}}", &year.to_string())
+            .replace("{{day}}", &day.to_string()),
+    )?;
+    write(&new_files[1], "")?;
+    write(&new_files[2], "")?;
+    write(&registry, &registered)?;
+
+    let mut changed = new_files.to_vec();
+    changed.push(registry);
+    if let Some(years) = years {
+        write(&years_registry, &years)?;
+        changed.push(years_registry);
+    }
+    Ok(changed)
+}
+
+// Adds the module declaration and the registry entry for the day to the text of the year's `mod.rs`,
+// keeping days in order. `None` if the day is already there or the registry can't be found
+pub fn register(registry: &str, day: u32) -> Option<String> {
+    add_to_registry(
+        registry,
+        day,
+        "day",
+        format!("&day{}::Day{}", day, day),
+        |e| e.strip_prefix("&day")?.split("::").next()?.parse().ok(),
+    )
+}
+
+// The same for a year in `days/mod.rs`
+pub fn register_year(registry: &str, year: u32) -> Option<String> {
+    add_to_registry(
+        registry,
+        year,
+        "y",
+        format!("({}, &y{}::PUZZLES)", year, year),
+        |e| e.strip_prefix('(')?.split(',').next()?.trim().parse().ok(),
+    )
+}
+
+// The registry is found by its syntax rather than its layout, and written back as rustfmt would, so it
+// doesn't matter whether it was formatted since it was last written
+fn add_to_registry(
+    registry: &str,
+    key: u32,
+    prefix: &str,
+    entry: String,
+    entry_key: impl Fn(&str) -> Option<u32>,
+) -> Option<String> {
+    let module_name = |l: &str| -> Option<String> {
+        let name = l.trim().strip_prefix("pub mod ")?.strip_suffix(';')?;
+        name.strip_prefix(prefix)?.parse::<u32>().ok()?;
+        Some(name.to_string())
+    };
+    let mut lines = registry.lines().map(|l| l.to_string()).collect::<Vec<_>>();
+    let mut modules = lines
+        .iter()
+        .filter_map(|l| module_name(l))
+        .collect::<Vec<_>>();
+    let module = format!("{}{}", prefix, key);
+    if modules.contains(&module) {
+        return None;
+    }
+
+    // the modules go together where the first one was, in the order rustfmt sorts them, or before the
+    // array and the comment above it, in a block of their own
+    let first = match lines.iter().position(|l| module_name(l).is_some()) {
+        Some(i) => i,
+        None => {
+            let mut i = lines.iter().position(|l| l.contains("static "))?;
+            while i > 0 && lines[i - 1].trim_start().starts_with("//") {
+                i -= 1;
+            }
+            lines.insert(i, String::new());
+            i
+        }
+    };
+    lines.retain(|l| module_name(l).is_none());
+    modules.push(module);
+    modules.sort();
+    for (i, name) in modules.iter().enumerate() {
+        lines.insert(first + i, format!("pub mod {};", name));
+    }
+    let text = lines.join("\n") + "\n";
+
+    let array = RegistryArray::find(&text)?;
+    let mut entries = array.entries(&text);
+    if entries.iter().any(|e| entry_key(e) == Some(key)) {
+        return None;
+    }
+    let at = entries
+        .iter()
+        .position(|e| entry_key(e).is_some_and(|k| k > key))
+        .unwrap_or(entries.len());
+    entries.insert(at, entry);
+    Some(array.replace(&text, &entries))
+}
+
+// Where the registry array is in the text, from the start of the line declaring it
+struct RegistryArray {
+    line_start: usize,
+    // the `N` in `[T; N]`
+    size: (usize, usize),
+    open: usize,
+    // just past the `;` after the closing bracket
+    end: usize,
+}
+
+impl RegistryArray {
+    fn find(text: &str) -> Option<RegistryArray> {
+        let declaration = text.find("static ")?;
+        let line_start = text[..declaration].rfind('\n').map_or(0, |i| i + 1);
+        let open = declaration + text[declaration..].find('=')? + 1;
+        let open = open + text[open..].find('[')?;
+        let size_end = text[..open].rfind(']')?;
+        let size_start = text[..size_end].rfind(';')? + 1;
+        text[size_start..size_end].trim().parse::<usize>().ok()?;
+
+        let mut depth = 0;
+        let close = text[open..].char_indices().find_map(|(i, c)| {
+            match c {
+                '[' | '(' => depth += 1,
+                ']' | ')' => depth -= 1,
+                _ => {}
+            }
+            (depth == 0).then_some(open + i)
+        })?;
+        let end = close + 1 + text[close + 1..].find(';')? + 1;
+        Some(RegistryArray {
+            line_start,
+            size: (size_start, size_end),
+            open,
+            end,
+        })
+    }
+
+    // the elements, split on the commas outside of brackets
+    fn entries(&self, text: &str) -> Vec<String> {
+        let mut entries = vec![String::new()];
+        let mut depth = 0;
+        for c in text[self.open + 1..self.end].chars() {
+            match c {
+                '[' | '(' => depth += 1,
+                ']' | ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    entries.push(String::new());
+                    continue;
+                }
+                _ => {}
+            }
+            if depth >= 0 {
+                entries.last_mut().unwrap().push(c);
+            }
+        }
+        entries
+            .iter()
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+            .collect()
+    }
+
+    // The text with the array holding `entries`: on one line when it fits in rustfmt's widths for arrays
+    // and lines, one element per line otherwise
+    fn replace(&self, text: &str, entries: &[String]) -> String {
+        let head = format!(
+            "{} {}{}",
+            &text[self.line_start..self.size.0],
+            entries.len(),
+            &text[self.size.1..self.open]
+        );
+        let indent = head.len() - head.trim_start().len();
+        let one_line = format!("[{}];", entries.join(", "));
+        let array = if one_line.len() - 1 <= ARRAY_WIDTH && head.len() + one_line.len() <= MAX_WIDTH
+        {
+            one_line
+        } else {
+            let items = entries
+                .iter()
+                .map(|e| format!("{}    {},\n", " ".repeat(indent), e))
+                .collect::<String>();
+            format!("[\n{}{}];", items, " ".repeat(indent))
+        };
+        format!(
+            "{}{}{}{}",
+            &text[..self.line_start],
+            head,
+            array,
+            &text[self.end..]
+        )
+    }
+}
+
+// rustfmt's defaults for `max_width` and `array_width`
+const MAX_WIDTH: usize = 100;
+const ARRAY_WIDTH: usize = 60;
+
+fn create_dir(path: &Path) -> Result<(), AocError> {
+    fs::create_dir_all(path).map_err(|source| AocError::Io {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), AocError> {
+    fs::write(path, contents).map_err(|source| AocError::Io {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "use crate::solution::Puzzle;
+
+pub mod day1;
+pub mod day2;
+
+pub static PUZZLES: [&dyn Puzzle; 2] = [&day1::Day1, &day2::Day2];
+";
+
+    const YEARS: &str = "use crate::solution::Puzzle;
+
+pub mod y2021;
+
+// Every year with solutions
+static YEARS: [(u32, &[&dyn Puzzle]); 1] = [(2021, &y2021::PUZZLES)];
+
+pub fn years() -> impl Iterator<Item = u32> {
+    YEARS.iter().map(|&(year, _)| year)
+}
+";
+
+    #[test]
+    fn registers_new_day() {
+        let registered = register(REGISTRY, 3).unwrap();
+        assert!(registered.contains("pub mod day2;\npub mod day3;\n"));
+        assert!(registered.contains(
+            "pub static PUZZLES: [&dyn Puzzle; 3] = [&day1::Day1, &day2::Day2, &day3::Day3];\n"
+        ));
+    }
+
+    // whether rustfmt wrapped the array and sorted the modules or not, the result is the same
+    #[test]
+    fn registers_whatever_the_layout() {
+        let days = (1..=9).filter(|&d| d != 4).collect::<Vec<u32>>();
+        let modules = |days: &[u32]| {
+            let mut names = days.iter().map(|d| format!("day{}", d)).collect::<Vec<_>>();
+            names.sort();
+            names
+                .iter()
+                .map(|n| format!("pub mod {};\n", n))
+                .collect::<String>()
+        };
+        let entries = days
+            .iter()
+            .map(|d| format!("&day{}::Day{}", d, d))
+            .collect::<Vec<_>>();
+        let one_line = format!(
+            "{}\npub static PUZZLES: [&dyn Puzzle; 8] = [{}];\n",
+            modules(&days),
+            entries.join(", ")
+        );
+        let wrapped = format!(
+            "{}\npub static PUZZLES: [&dyn Puzzle; 8] = [\n    {},\n];\n",
+            modules(&days),
+            entries.join(",\n    ")
+        );
+
+        let expected = "pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day3;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+pub static PUZZLES: [&dyn Puzzle; 9] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+];
+";
+        assert_eq!(register(&one_line, 10).unwrap(), expected);
+        assert_eq!(register(&wrapped, 10).unwrap(), expected);
+        let with_4 = register(expected, 4).unwrap();
+        assert!(with_4.contains("pub mod day3;\npub mod day4;\npub mod day5;\n"));
+        assert!(with_4.contains("[&dyn Puzzle; 10] = [\n"));
+        assert!(with_4.contains("    &day3::Day3,\n    &day4::Day4,\n    &day5::Day5,\n"));
+        assert_eq!(register(&wrapped, 9), None);
+
+        let years = register_year(YEARS, 2023).unwrap();
+        assert!(years.contains("pub mod y2021;\npub mod y2023;\n\n// Every year"));
+        assert!(years.contains(
+            "[(u32, &[&dyn Puzzle]); 2] = [(2021, &y2021::PUZZLES), (2023, &y2023::PUZZLES)];\n\npub fn years()"
+        ));
+        let years = register_year(&register_year(&years, 2022).unwrap(), 2020).unwrap();
+        assert!(years.contains("[(u32, &[&dyn Puzzle]); 4] = [\n    (2020, &y2020::PUZZLES),\n    (2021, &y2021::PUZZLES),\n    (2022, &y2022::PUZZLES),\n    (2023, &y2023::PUZZLES),\n];\n"));
+    }
+
+    #[test]
+    fn registers_day_before_the_others() {
+        let registry = "pub mod day10;\npub mod day2;\n\npub static PUZZLES: [&dyn Puzzle; 2] = [&day2::Day2, &day10::Day10];\n";
+        assert_eq!(
+            register(registry, 1).unwrap(),
+            "pub mod day1;\npub mod day10;\npub mod day2;\n\npub static PUZZLES: [&dyn Puzzle; 3] = [&day1::Day1, &day2::Day2, &day10::Day10];\n"
+        );
+        assert_eq!(register(registry, 10), None);
+    }
+
+    #[test]
+    fn refuses_registered_day() {
+        assert_eq!(register(REGISTRY, 2), None);
+        assert_eq!(register_year(YEARS, 2021), None);
+    }
+
+    #[test]
+    fn never_overwrites_files() {
+        let project = std::env::temp_dir().join(format!(
+            "aoc-scaffold-{}-never_overwrites_files",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&project);
+        let resources = project.join("resources");
+        fs::create_dir_all(project.join("src/days/y2021")).unwrap();
+        fs::create_dir_all(year_path(&resources, 2021)).unwrap();
+        fs::write(project.join("src/days/mod.rs"), YEARS).unwrap();
+        fs::write(project.join("src/days/y2021/mod.rs"), REGISTRY).unwrap();
+        fs::write(input_path(&resources, 2021, 3), "keep").unwrap();
+
+        let result = new_day(&project, &resources, 2021, 3);
+        assert!(matches!(result, Err(AocError::AlreadyExists { .. })));
+        assert_eq!(
+            fs::read_to_string(input_path(&resources, 2021, 3)).unwrap(),
+            "keep"
+        );
+        assert!(!day_path(&project, 2021, 3).exists());
+
+        fs::remove_file(input_path(&resources, 2021, 3)).unwrap();
+        new_day(&project, &resources, 2021, 3).unwrap();
+        assert!(fs::read_to_string(day_path(&project, 2021, 3))
+            .unwrap()
+            .contains("const YEAR: u32 = 2021;\n    const DAY: u32 = 3;"));
+        fs::remove_dir_all(project).unwrap();
+    }
+
+    #[test]
+    fn starts_new_year() {
+        let project = std::env::temp_dir().join(format!(
+            "aoc-scaffold-{}-starts_new_year",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&project);
+        let resources = project.join("resources");
+        fs::create_dir_all(project.join("src/days")).unwrap();
+        fs::write(project.join("src/days/mod.rs"), YEARS).unwrap();
+
+        new_day(&project, &resources, 2022, 1).unwrap();
+        assert_eq!(
+            fs::read_to_string(project.join("src/days/y2022/mod.rs")).unwrap(),
+            "use crate::solution::Puzzle;
+
+pub mod day1;
+
+pub static PUZZLES: [&dyn Puzzle; 1] = [&day1::Day1];
+"
+        );
+        let years = fs::read_to_string(project.join("src/days/mod.rs")).unwrap();
+        assert!(years.contains("pub mod y2021;\npub mod y2022;\n"));
+        assert!(years.contains(
+            "[(u32, &[&dyn Puzzle]); 2] = [(2021, &y2021::PUZZLES), (2022, &y2022::PUZZLES)];"
+        ));
+        assert!(input_path(&resources, 2022, 1).exists());
+        fs::remove_dir_all(project).unwrap();
+    }
+}
@@ -0,0 +1,163 @@
+use crate::error::AocError;
+use crate::helpers::*;
+use crate::http::Request;
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+// environment variables overriding the site and holding the session cookie
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const DEFAULT_YEAR: u32 = 2021;
+
+// The Advent of Code site, or anything speaking like it, with the session of a logged in user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Site {
+    pub base_url: String,
+    pub session: String,
+}
+
+impl Site {
+    // The base url is taken from the command line, then the environment, then the real site. The
+    // session comes from the environment or, failing that, the session file
+    pub fn from_env(base_url: Option<&str>) -> Result<Site, AocError> {
+        let base_url = base_url
+            .map(|s| s.to_string())
+            .or_else(|| env::var(BASE_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        let session = match env::var(SESSION_ENV) {
+            Ok(session) => session,
+            Err(_) => read_session_file(&session_path())?,
+        };
+        Ok(Site::new(&base_url, session.trim()))
+    }
+
+    pub fn new(base_url: &str, session: &str) -> Site {
+        Site {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    pub fn day_url(&self, year: u32, day: u32) -> String {
+        format!("{}/{}/day/{}", self.base_url, year, day)
+    }
+
+    pub fn request(&self, request: Request) -> Request {
+        request.with_cookie(&format!("session={}", self.session))
+    }
+}
+
+// `~/.config/aoc/session`, holding nothing but the value of the session cookie
+pub fn session_path() -> PathBuf {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    Path::new(&home).join(".config/aoc/session")
+}
+
+fn read_session_file(path: &Path) -> Result<String, AocError> {
+    let session = read_file(path)
+        .map_err(|_| AocError::MissingSession {
+            path: path.display().to_string(),
+        })?
+        .join("");
+    if session.trim().is_empty() {
+        return Err(AocError::MissingSession {
+            path: path.display().to_string(),
+        });
+    }
+    Ok(session)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded,
+    // the input was already in the resources folder, so nothing was requested
+    Cached,
+}
+
+// Whether the input for the day is already in the resources folder. Empty files (like the placeholders
+// written for a new day) don't count as a downloaded input
+pub fn has_input(root: &Path, year: u32, day: u32) -> bool {
+    fs::metadata(input_path(root, year, day)).is_ok_and(|m| m.len() > 0)
+}
+
+// Downloads the input for the day into the resources folder, unless it is already there
+pub fn fetch_input(site: &Site, root: &Path, year: u32, day: u32) -> Result<Fetched, AocError> {
+    let path = input_path(root, year, day);
+    if has_input(root, year, day) {
+        return Ok(Fetched::Cached);
+    }
+
+    let url = format!("{}/input", site.day_url(year, day));
+    let response = site.request(Request::get(&url)).send()?;
+    let http_error = |message: &str| AocError::Http {
+        url: url.clone(),
+        message: format!("{} (status {})", message, response.status),
+    };
+    match response.status {
+        200 => {}
+        404 => return Err(http_error("the puzzle isn't available yet")),
+        400 | 401 | 403 | 500 => return Err(http_error("the session was not accepted")),
+        _ => return Err(http_error("unexpected response")),
+    }
+
+    let io_error = |source| AocError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+    // the first input of a year starts its folder
+    fs::create_dir_all(year_path(root, year)).map_err(io_error)?;
+    fs::write(&path, response.body).map_err(io_error)?;
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::stub;
+
+    fn resources(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-site-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn fetches_once_and_caches() {
+        let server = stub::serve(vec![(200, "199\n200\n")]);
+        let site = Site::new(&server.base_url, "secret");
+        let root = resources("fetches_once_and_caches");
+
+        assert_eq!(
+            fetch_input(&site, &root, 2021, 1).unwrap(),
+            Fetched::Downloaded
+        );
+        let request = server.requests.recv().unwrap();
+        assert!(request.starts_with("GET /2021/day/1/input "));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        assert_eq!(
+            fs::read_to_string(input_path(&root, 2021, 1)).unwrap(),
+            "199\n200\n"
+        );
+
+        // the stub only answers once, a second download would fail
+        assert_eq!(fetch_input(&site, &root, 2021, 1).unwrap(), Fetched::Cached);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn reports_unavailable_puzzle() {
+        let server = stub::serve(vec![(404, "not yet")]);
+        let site = Site::new(&server.base_url, "secret");
+        let root = resources("reports_unavailable_puzzle");
+
+        let result = fetch_input(&site, &root, 2021, 25);
+        assert!(matches!(result, Err(AocError::Http { .. })));
+        assert!(!input_path(&root, 2021, 25).exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,171 @@
+use crate::error::AocError;
+use crate::memory::{self, Usage};
+use crate::numeric::{self, Policy};
+
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn from_number(n: u32) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+// Every day follows the same shape: turn the raw lines into some input, then solve both parts with it.
+pub trait Solution {
+    type Input: Clone;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    const YEAR: u32;
+    const DAY: u32;
+    // bumped whenever a change to the solution may change its answers, so cached ones aren't used
+    const VERSION: &'static str = "1";
+
+    fn parse(input: Vec<String>) -> Result<Self::Input, AocError>;
+    fn part_1(input: Self::Input) -> Self::Answer1;
+    fn part_2(input: Self::Input) -> Self::Answer2;
+
+    // answers for the sample input, used to check the solution before running the real input
+    fn sample_answers() -> (Self::Answer1, Self::Answer2);
+}
+
+// Outcome of parsing an input once and solving some of its parts, with the time spent on each phase and,
+// when allocations are being counted, the memory
+#[derive(Debug, Clone)]
+pub struct Execution {
+    pub parse_time: Duration,
+    pub parse_memory: Option<Usage>,
+    pub results: Vec<PartResult>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: String,
+    pub time: Duration,
+    pub memory: Option<Usage>,
+}
+
+// Days have different input and answer types, so the registry stores them through this object safe view
+// where answers are already rendered as strings.
+pub trait Puzzle: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn version(&self) -> &'static str;
+    fn solve(&self, input: Vec<String>, part: Part, overflow: Policy) -> Result<String, AocError>;
+    // solves the parts, with `overflow` telling whether arithmetic that overflows fails them
+    fn execute(
+        &self,
+        input: Vec<String>,
+        parts: &[Part],
+        overflow: Policy,
+    ) -> Result<Execution, AocError>;
+    fn sample_answer(&self, part: Part) -> String;
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn year(&self) -> u32 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn version(&self) -> &'static str {
+        S::VERSION
+    }
+
+    fn solve(&self, input: Vec<String>, part: Part, overflow: Policy) -> Result<String, AocError> {
+        let execution = self.execute(input, &[part], overflow)?;
+        Ok(execution.results[0].answer.clone())
+    }
+
+    fn execute(
+        &self,
+        input: Vec<String>,
+        parts: &[Part],
+        overflow: Policy,
+    ) -> Result<Execution, AocError> {
+        numeric::with_policy(overflow, || execute::<S>(input, parts))
+    }
+
+    fn sample_answer(&self, part: Part) -> String {
+        let (answer_1, answer_2) = S::sample_answers();
+        match part {
+            Part::One => answer_1.to_string(),
+            Part::Two => answer_2.to_string(),
+        }
+    }
+}
+
+fn execute<S: Solution>(input: Vec<String>, parts: &[Part]) -> Result<Execution, AocError> {
+    numeric::take_overflow();
+    let start = Instant::now();
+    let (parsed, parse_memory) = memory::measure(|| S::parse(input));
+    let parse_time = start.elapsed();
+    let parsed = parsed?;
+    if let Some(operation) = numeric::take_overflow() {
+        return Err(AocError::Overflow {
+            day: S::DAY,
+            part: None,
+            operation,
+        });
+    }
+
+    let mut results = Vec::new();
+    for &part in parts {
+        // every part gets its own copy of the input, cloned outside of the timing
+        let input = parsed.clone();
+        numeric::take_overflow();
+        let start = Instant::now();
+        let (answer, memory) = memory::measure(|| match part {
+            Part::One => S::part_1(input).to_string(),
+            Part::Two => S::part_2(input).to_string(),
+        });
+        // with overflows checked, the answer of a part that overflowed can't be trusted
+        if let Some(operation) = numeric::take_overflow() {
+            return Err(AocError::Overflow {
+                day: S::DAY,
+                part: Some(part),
+                operation,
+            });
+        }
+        results.push(PartResult {
+            part,
+            answer,
+            time: start.elapsed(),
+            memory,
+        });
+    }
+
+    Ok(Execution {
+        parse_time,
+        parse_memory,
+        results,
+    })
+}
@@ -0,0 +1,363 @@
+use crate::error::AocError;
+use crate::helpers::*;
+use crate::http::Request;
+use crate::site::Site;
+use crate::solution::Part;
+
+use std::{
+    fmt::Display,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+// What the site said about a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // with how long to wait, when the site says it
+    RateLimited(Option<String>),
+    // the part was already solved, so the answer wasn't checked
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    // reads the verdict out of the page returned after posting an answer
+    pub fn from_page(page: &str) -> Verdict {
+        if page.contains("That's the right answer") {
+            Verdict::Right
+        } else if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if page.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if page.contains("You gave an answer too recently") {
+            let wait = page
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_string());
+            Verdict::RateLimited(wait)
+        } else if page.contains("Did you already complete it") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::RateLimited(_) => "rate-limited",
+            Verdict::AlreadySolved => "solved",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        match name {
+            "right" => Some(Verdict::Right),
+            "wrong" => Some(Verdict::Wrong),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "rate-limited" => Some(Verdict::RateLimited(None)),
+            "solved" => Some(Verdict::AlreadySolved),
+            "unknown" => Some(Verdict::Unknown),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "that's the right answer"),
+            Verdict::Wrong => write!(f, "that's not the right answer"),
+            Verdict::TooHigh => write!(f, "that's not the right answer, it is too high"),
+            Verdict::TooLow => write!(f, "that's not the right answer, it is too low"),
+            Verdict::RateLimited(Some(wait)) => write!(f, "answered too recently, wait {}", wait),
+            Verdict::RateLimited(None) => write!(f, "answered too recently"),
+            Verdict::AlreadySolved => write!(f, "this part is already solved"),
+            Verdict::Unknown => write!(f, "the response wasn't understood"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+// Every answer submitted, kept in `submissions.txt` in the resources folder as
+// `<year> <day> <part> <verdict> <answer>` lines
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<History, AocError> {
+        match read_file(path) {
+            Ok(lines) => History::parse(lines).map_err(|e| e.in_file(&path.display().to_string())),
+            Err(AocError::MissingFile { .. }) => Ok(History::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn parse(input: Vec<String>) -> Result<History, AocError> {
+        let mut attempts = Vec::new();
+        for (i, line) in input.iter().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.splitn(5, ' ').collect::<Vec<_>>();
+            if fields.len() != 5 {
+                return Err(line_error(
+                    line,
+                    i,
+                    "expected '<year> <day> <part> <verdict> <answer>'",
+                ));
+            }
+            let part = parse_value::<u32>(line, i, fields[2])?;
+            let part = Part::from_number(part).ok_or_else(|| {
+                AocError::parse(
+                    i + 1,
+                    column_of(line, fields[2]),
+                    fields[2],
+                    "part must be 1 or 2",
+                )
+            })?;
+            let verdict = Verdict::from_name(fields[3]).ok_or_else(|| {
+                AocError::parse(
+                    i + 1,
+                    column_of(line, fields[3]),
+                    fields[3],
+                    "unknown verdict",
+                )
+            })?;
+            attempts.push(Attempt {
+                year: parse_value(line, i, fields[0])?,
+                day: parse_value(line, i, fields[1])?,
+                part,
+                verdict,
+                answer: unescape(fields[4]),
+            });
+        }
+        Ok(History { attempts })
+    }
+
+    pub fn append(path: &Path, attempt: &Attempt) -> Result<(), AocError> {
+        let io_error = |source| AocError::Io {
+            path: path.display().to_string(),
+            source,
+        };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(io_error)?;
+        writeln!(
+            file,
+            "{} {} {} {} {}",
+            attempt.year,
+            attempt.day,
+            attempt.part,
+            attempt.verdict.name(),
+            escape(&attempt.answer)
+        )
+        .map_err(io_error)
+    }
+
+    // The reason not to submit an answer, if the history already tells how it would go: the part is
+    // solved, the answer was wrong before, or it falls outside the bounds of answers that were too
+    // high or too low
+    pub fn refusal(&self, year: u32, day: u32, part: Part, answer: &str) -> Option<String> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|a| a.year == year && a.day == day && a.part == part)
+            .collect::<Vec<_>>();
+
+        if let Some(right) = attempts.iter().find(|a| a.verdict == Verdict::Right) {
+            return Some(format!("already solved with {}", right.answer));
+        }
+        if let Some(previous) = attempts.iter().find(|a| a.answer == answer) {
+            if matches!(
+                previous.verdict,
+                Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow
+            ) {
+                return Some(format!(
+                    "{} was already submitted: {}",
+                    answer, previous.verdict
+                ));
+            }
+        }
+
+        let value = answer.parse::<i128>().ok()?;
+        let bound = |verdict: Verdict| {
+            attempts
+                .iter()
+                .filter(move |a| a.verdict == verdict)
+                .filter_map(|a| a.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = bound(Verdict::TooHigh).filter(|&h| value >= h).min() {
+            return Some(format!("{} was already too high", high));
+        }
+        if let Some(low) = bound(Verdict::TooLow).filter(|&l| value <= l).max() {
+            return Some(format!("{} was already too low", low));
+        }
+        None
+    }
+}
+
+pub fn history_path(root: &Path) -> PathBuf {
+    root.join("submissions.txt")
+}
+
+// Posts the answer unless the history says it can't be right, recording the attempt
+pub fn submit(
+    site: &Site,
+    history_file: &Path,
+    year: u32,
+    day: u32,
+    part: Part,
+    answer: &str,
+) -> Result<Verdict, AocError> {
+    let history = History::load(history_file)?;
+    if let Some(reason) = history.refusal(year, day, part, answer) {
+        return Err(AocError::Refused { reason });
+    }
+
+    let url = format!("{}/answer", site.day_url(year, day));
+    let form = vec![
+        ("level".to_string(), part.to_string()),
+        ("answer".to_string(), answer.to_string()),
+    ];
+    let response = site.request(Request::post(&url, form)).send()?;
+    if response.status != 200 {
+        return Err(AocError::Http {
+            url,
+            message: format!("unexpected response (status {})", response.status),
+        });
+    }
+
+    let verdict = Verdict::from_page(&response.body);
+    History::append(
+        history_file,
+        &Attempt {
+            year,
+            day,
+            part,
+            verdict: verdict.clone(),
+            answer: answer.to_string(),
+        },
+    )?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::stub;
+    use std::fs;
+
+    fn history(lines: &[&str]) -> History {
+        History::parse(lines.iter().map(|s| s.to_string()).collect()).unwrap()
+    }
+
+    #[test]
+    fn reads_verdicts() {
+        let page = "<p>That's not the right answer; your answer is too low.</p>";
+        assert_eq!(Verdict::from_page(page), Verdict::TooLow);
+        let page = "You gave an answer too recently. You have 4m 30s left to wait.";
+        assert_eq!(
+            Verdict::from_page(page),
+            Verdict::RateLimited(Some("4m 30s".to_string()))
+        );
+        assert_eq!(
+            Verdict::from_page("That's the right answer!"),
+            Verdict::Right
+        );
+    }
+
+    #[test]
+    fn refuses_known_answers_and_bounds() {
+        let history = history(&[
+            "2021 1 1 too-high 2000",
+            "2021 1 1 too-low 1000",
+            "2021 1 1 wrong 1500",
+            "2021 2 1 right 42",
+        ]);
+        let refusal = |day, answer| history.refusal(2021, day, Part::One, answer);
+        assert!(refusal(1, "1500").is_some());
+        assert!(refusal(1, "2500").is_some());
+        assert!(refusal(1, "999").is_some());
+        assert!(refusal(1, "1501").is_none());
+        assert!(refusal(2, "43").is_some());
+        assert!(history.refusal(2021, 1, Part::Two, "2500").is_none());
+    }
+
+    #[test]
+    fn records_attempts() {
+        let server = stub::serve(vec![(
+            200,
+            "That's not the right answer; your answer is too high.",
+        )]);
+        let site = Site::new(&server.base_url, "secret");
+        let path = std::env::temp_dir().join(format!(
+            "aoc-submissions-{}-records_attempts.txt",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let verdict = submit(&site, &path, 2021, 3, Part::Two, "900").unwrap();
+        assert_eq!(verdict, Verdict::TooHigh);
+        let request = server.requests.recv().unwrap();
+        assert!(request.starts_with("POST /2021/day/3/answer "));
+        assert!(request.ends_with("level=2&answer=900"));
+
+        // the stub is gone, so this only works because the history refuses it
+        let result = submit(&site, &path, 2021, 3, Part::Two, "901");
+        assert!(matches!(result, Err(AocError::Refused { .. })));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "2021 3 2 too-high 900\n"
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reads_back_escaped_answers() {
+        let path = std::env::temp_dir().join(format!(
+            "aoc-submissions-{}-reads_back_escaped_answers.txt",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let attempt = Attempt {
+            year: 2021,
+            day: 13,
+            part: Part::Two,
+            verdict: Verdict::Wrong,
+            answer: "#.\\n\n.#\\".to_string(),
+        };
+        History::append(&path, &attempt).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "2021 13 2 wrong #.\\\\n\\n.#\\\\\n"
+        );
+        assert_eq!(History::load(&path).unwrap().attempts, vec![attempt]);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    env,
+    fmt::{Arguments, Display},
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        RwLock,
+    },
+};
+
+// Where the filter comes from when `--trace` isn't given, with the same syntax
+pub const TRACE_ENV: &str = "AOC_TRACE";
+
+// From the least to the most verbose, a filter at a level lets the ones before it through too
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    // what a solver found out, once per part
+    Info = 1,
+    // the steps a solver goes through
+    Debug,
+    // the state inside loops and recursion, which can be a lot
+    Trace,
+}
+
+impl Level {
+    pub fn from_name(name: &str) -> Option<Level> {
+        match name {
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Info => write!(f, "info"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+// Which messages are shown, as comma separated `<target>=<level>` items where the target is the
+// module a message comes from (`day16`, `runner`...). A level on its own applies to every target
+// not named, and a target on its own shows all of its messages
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    default: Option<Level>,
+    targets: Vec<(String, Level)>,
+}
+
+impl Filter {
+    pub fn parse(spec: &str) -> Result<Filter, String> {
+        let mut filter = Filter::default();
+        for item in spec.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            match item.split_once('=') {
+                Some((target, level)) => {
+                    let level = Level::from_name(level.trim())
+                        .ok_or(format!("unknown trace level: {}", level))?;
+                    filter.targets.push((target.trim().to_string(), level));
+                }
+                None => match Level::from_name(item) {
+                    Some(level) => filter.default = Some(level),
+                    None => filter.targets.push((item.to_string(), Level::Trace)),
+                },
+            }
+        }
+        Ok(filter)
+    }
+
+    // the most verbose level shown for a target, the last item naming it winning
+    pub fn level_for(&self, target: &str) -> Option<Level> {
+        self.targets
+            .iter()
+            .rev()
+            .find(|(t, _)| t == target)
+            .map(|&(_, level)| level)
+            .or(self.default)
+    }
+
+    fn max_level(&self) -> Option<Level> {
+        self.targets
+            .iter()
+            .map(|&(_, level)| level)
+            .chain(self.default)
+            .max()
+    }
+}
+
+static FILTER: RwLock<Filter> = RwLock::new(Filter {
+    default: None,
+    targets: Vec::new(),
+});
+// the most verbose level any target shows, 0 for none, so messages nobody asked for cost a load
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_filter(filter: Filter) {
+    let max = filter.max_level().map_or(0, |level| level as u8);
+    *FILTER.write().unwrap_or_else(|e| e.into_inner()) = filter;
+    MAX_LEVEL.store(max, Ordering::Relaxed);
+}
+
+// Takes the filter from the environment, if there is one
+pub fn init_from_env() -> Result<(), String> {
+    if let Ok(spec) = env::var(TRACE_ENV) {
+        set_filter(Filter::parse(&spec).map_err(|e| format!("{}: {}", TRACE_ENV, e))?);
+    }
+    Ok(())
+}
+
+// The module a message comes from, out of its path
+pub fn target(module_path: &str) -> &str {
+    module_path.rsplit("::").next().unwrap_or(module_path)
+}
+
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    if level as u8 > MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+    let filter = FILTER.read().unwrap_or_else(|e| e.into_inner());
+    filter
+        .level_for(target(module_path))
+        .is_some_and(|shown| level <= shown)
+}
+
+// Called by the macros below once the message is known to be shown
+pub fn write(level: Level, module_path: &str, message: Arguments) {
+    eprintln!("[{} {}] {}", target(module_path), level, message);
+}
+
+#[macro_export]
+macro_rules! log_at {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($level, module_path!()) {
+            $crate::trace::write($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log_at!($crate::trace::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log_at!($crate::trace::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log_at!($crate::trace::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_by_target_and_level() {
+        let filter = Filter::parse("info, day16=debug,day3").unwrap();
+        assert_eq!(filter.level_for("day16"), Some(Level::Debug));
+        assert_eq!(filter.level_for("day3"), Some(Level::Trace));
+        assert_eq!(filter.level_for("day4"), Some(Level::Info));
+        assert_eq!(filter.max_level(), Some(Level::Trace));
+
+        let filter = Filter::parse("day16=debug").unwrap();
+        assert_eq!(filter.level_for("day4"), None);
+        assert!(Filter::parse("day16=loud").is_err());
+        assert_eq!(target("aoc_2021_rust::days::day16"), "day16");
+    }
+}
@@ -0,0 +1,261 @@
+use crate::error::AocError;
+use crate::helpers::*;
+use crate::numeric::Policy;
+use crate::pool;
+use crate::solution::{Part, Puzzle};
+
+use std::{collections::HashMap, fmt::Display, path::Path};
+
+// Expected answers for the real inputs, read from `answers.txt` in the resources folder.
+// Each line is `<day> <part> <answer>`, empty lines and lines starting with `#` are skipped
+#[derive(Debug, Clone, Default)]
+pub struct Answers {
+    expected: HashMap<(u32, Part), String>,
+}
+
+impl Answers {
+    pub fn load(root: &Path, year: u32) -> Result<Answers, AocError> {
+        let path = answers_path(root, year);
+        Answers::parse(read_file(&path)?).map_err(|e| e.in_file(&path.display().to_string()))
+    }
+
+    pub fn parse(input: Vec<String>) -> Result<Answers, AocError> {
+        let mut expected = HashMap::new();
+        for (i, line) in input.iter().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.splitn(3, ' ').collect::<Vec<_>>();
+            if fields.len() != 3 {
+                return Err(line_error(line, i, "expected '<day> <part> <answer>'"));
+            }
+            let day = parse_value::<u32>(line, i, fields[0])?;
+            let part = parse_value::<u32>(line, i, fields[1])?;
+            let part = Part::from_number(part).ok_or_else(|| {
+                AocError::parse(
+                    i + 1,
+                    column_of(line, fields[1]),
+                    fields[1],
+                    "part must be 1 or 2",
+                )
+            })?;
+            // multi line answers are stored with escaped line breaks
+            expected.insert((day, part), unescape(fields[2]));
+        }
+
+        Ok(Answers { expected })
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&String> {
+        self.expected.get(&(day, part))
+    }
+
+    // the answer the day had before, if any
+    pub fn insert(&mut self, day: u32, part: Part, answer: String) -> Option<String> {
+        self.expected.insert((day, part), answer)
+    }
+
+    // the lines `parse` reads back, sorted by day and part
+    pub fn to_text(&self) -> String {
+        let mut entries = self.expected.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|((day, part), _)| (*day, *part == Part::Two));
+        entries
+            .into_iter()
+            .map(|((day, part), answer)| format!("{} {} {}\n", day, part, escape(answer)))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+    Error(String),
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Missing => write!(f, "missing"),
+            Status::Error(_) => write!(f, "ERROR"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub day: u32,
+    pub part: Part,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+}
+
+impl Check {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status, Status::Fail | Status::Error(_))
+    }
+}
+
+// runs every part of the given days against the real input and compares with the expected answers,
+// checking `jobs` days at a time
+pub fn verify(
+    puzzles: &[&dyn Puzzle],
+    parts: &[Part],
+    answers: &Answers,
+    root: &Path,
+    overflow: Policy,
+    jobs: usize,
+) -> Vec<Check> {
+    let mut checks = Vec::new();
+    pool::run_ordered(
+        puzzles,
+        jobs,
+        |&puzzle| verify_day(puzzle, parts, answers, root, overflow),
+        |puzzle, result| match result {
+            Ok(day_checks) => checks.extend(day_checks),
+            // a day that panics fails all its parts
+            Err(message) => checks.extend(parts.iter().map(|&part| Check {
+                day: puzzle.day(),
+                part,
+                status: Status::Error(message.clone()),
+                answer: None,
+                expected: answers.get(puzzle.day(), part).cloned(),
+            })),
+        },
+    );
+    checks
+}
+
+fn verify_day(
+    puzzle: &dyn Puzzle,
+    parts: &[Part],
+    answers: &Answers,
+    root: &Path,
+    overflow: Policy,
+) -> Vec<Check> {
+    let (year, day) = (puzzle.year(), puzzle.day());
+    let input = input_data(root, year, day);
+    parts
+        .iter()
+        .map(|&part| {
+            let expected = answers.get(day, part).cloned();
+            let answer = input.as_ref().map_err(|e| e.to_string()).and_then(|lines| {
+                puzzle.solve(lines.clone(), part, overflow).map_err(|e| {
+                    e.in_file(&input_path(root, year, day).display().to_string())
+                        .to_string()
+                })
+            });
+
+            let status = match (&answer, &expected) {
+                (Err(e), _) => Status::Error(e.clone()),
+                (Ok(_), None) => Status::Missing,
+                (Ok(a), Some(e)) if a == e => Status::Pass,
+                (Ok(_), Some(_)) => Status::Fail,
+            };
+            Check {
+                day,
+                part,
+                status,
+                answer: answer.ok(),
+                expected,
+            }
+        })
+        .collect()
+}
+
+pub fn print_table(checks: &[Check]) {
+    // multi line answers only show their first line to keep the table readable
+    let first_line = |s: &Option<String>| {
+        s.as_deref()
+            .map(|s| s.lines().next().unwrap_or("").to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    println!(
+        "{:>3} {:>4}  {:<7}  {:<20}  {:<20}",
+        "day", "part", "status", "answer", "expected"
+    );
+    for check in checks {
+        println!(
+            "{:>3} {:>4}  {:<7}  {:<20}  {:<20}",
+            check.day,
+            check.part,
+            check.status.to_string(),
+            first_line(&check.answer),
+            first_line(&check.expected)
+        );
+        if let Status::Error(e) = &check.status {
+            println!("          {}", e);
+        }
+    }
+
+    let count = |f: fn(&Status) -> bool| checks.iter().filter(|c| f(&c.status)).count();
+    println!(
+        "{} passed, {} failed, {} missing, {} errors",
+        count(|s| *s == Status::Pass),
+        count(|s| *s == Status::Fail),
+        count(|s| *s == Status::Missing),
+        count(|s| matches!(s, Status::Error(_)))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_manifest() {
+        let lines = vec![
+            "# comment".to_string(),
+            "".to_string(),
+            "1 2 1543".to_string(),
+            "13 2 #..\\n.#.".to_string(),
+        ];
+        let answers = Answers::parse(lines).unwrap();
+        assert_eq!(answers.get(1, Part::Two).unwrap(), "1543");
+        assert_eq!(answers.get(13, Part::Two).unwrap(), "#..\n.#.");
+        assert_eq!(answers.get(1, Part::One), None);
+    }
+
+    #[test]
+    fn inserts_and_writes_manifest() {
+        let mut answers = Answers::parse(vec!["13 2 #..\\n.#.".to_string()]).unwrap();
+        assert_eq!(answers.insert(1, Part::Two, "1543".to_string()), None);
+        assert_eq!(
+            answers.insert(1, Part::Two, "1600".to_string()),
+            Some("1543".to_string())
+        );
+        assert_eq!(answers.to_text(), "1 2 1600\n13 2 #..\\n.#.\n");
+        assert_eq!(
+            Answers::parse(answers.to_text().lines().map(|l| l.to_string()).collect())
+                .unwrap()
+                .get(13, Part::Two)
+                .unwrap(),
+            "#..\n.#."
+        );
+    }
+
+    #[test]
+    fn round_trips_backslashes() {
+        let mut answers = Answers::parse(Vec::new()).unwrap();
+        answers.insert(13, Part::Two, "a\\nb\n\\".to_string());
+        assert_eq!(answers.to_text(), "13 2 a\\\\nb\\n\\\\\n");
+        assert_eq!(
+            Answers::parse(answers.to_text().lines().map(|l| l.to_string()).collect())
+                .unwrap()
+                .get(13, Part::Two)
+                .unwrap(),
+            "a\\nb\n\\"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_part() {
+        let result = Answers::parse(vec!["1 3 10".to_string()]);
+        assert!(matches!(result, Err(AocError::Parse { column: 3, .. })));
+    }
+}
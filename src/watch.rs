@@ -0,0 +1,267 @@
+use crate::error::AocError;
+use crate::helpers::*;
+use crate::numeric::Policy;
+use crate::output::Record;
+use crate::runner::{self, DayOutcome};
+use crate::solution::{Part, Puzzle};
+use crate::verify::Answers;
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+// How often the files are looked at
+pub const POLL: Duration = Duration::from_millis(500);
+// Answers of the run before a restart, handed to the rebuilt binary
+pub const PREVIOUS_ENV: &str = "AOC_WATCH_PREVIOUS";
+
+// Modification times of some files, to tell when they change. A missing file counts as a time too, so
+// creating or removing one is a change
+#[derive(Debug, Clone)]
+pub struct Stamps {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Stamps {
+    pub fn new(paths: Vec<PathBuf>) -> Stamps {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let time = modified(&path);
+                (path, time)
+            })
+            .collect();
+        Stamps { files }
+    }
+
+    // the files changed since the last look, remembering their new times
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, time) in self.files.iter_mut() {
+            let now = modified(path);
+            if now != *time {
+                *time = now;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// The files a day is read from: its sample, and its input unless it doesn't come from a file
+pub fn watched_files(puzzle: &dyn Puzzle, source: &InputSource, root: &Path) -> Vec<PathBuf> {
+    let (year, day) = (puzzle.year(), puzzle.day());
+    let mut files = vec![sample_path(root, year, day)];
+    match source {
+        InputSource::Resources => files.push(input_path(root, year, day)),
+        InputSource::File(path) => files.push(path.clone()),
+        InputSource::Stdin | InputSource::Inline(_) => {}
+    }
+    files
+}
+
+// How an answer compares with the one the previous run got
+pub fn describe(record: &Record, previous: Option<&String>) -> String {
+    let sample = if record.sample_passed {
+        ""
+    } else {
+        " (the sample doesn't match)"
+    };
+    let multi_line = record.answer.contains('\n');
+    match previous {
+        Some(before) if *before == record.answer && multi_line => {
+            format!("part {}: unchanged{}", record.part, sample)
+        }
+        Some(before) if *before == record.answer => {
+            format!(
+                "part {}: {} (unchanged){}",
+                record.part, record.answer, sample
+            )
+        }
+        Some(before) if multi_line || before.contains('\n') => format!(
+            "part {}: changed{}, was:\n{}\nnow:\n{}",
+            record.part, sample, before, record.answer
+        ),
+        Some(before) => format!(
+            "part {}: {} (was {}){}",
+            record.part, record.answer, before, sample
+        ),
+        None if multi_line => format!("part {}:{}\n{}", record.part, sample, record.answer),
+        None => format!("part {}: {}{}", record.part, record.answer, sample),
+    }
+}
+
+// Runs the days, then again each time their files change, showing how the answers changed since the
+// previous run. When `exe` is given and the file changes, which is what rebuilding the binary does,
+// the new binary takes over with the same arguments. Only returns when that fails
+pub fn watch(
+    puzzles: &[&dyn Puzzle],
+    parts: &[Part],
+    source: &InputSource,
+    root: &Path,
+    overflow: Policy,
+    exe: Option<&Path>,
+) -> Result<(), AocError> {
+    let mut answers = previous_answers();
+    let mut stamps = puzzles
+        .iter()
+        .map(|&p| Stamps::new(watched_files(p, source, root)))
+        .collect::<Vec<_>>();
+    let mut binary = exe.map(|exe| Stamps::new(vec![exe.to_path_buf()]));
+    let mut pending = puzzles.to_vec();
+
+    loop {
+        for &puzzle in &pending {
+            let outcome = runner::run_day(puzzle, parts, source, root, false, None, overflow);
+            print_changes(&outcome, &mut answers);
+        }
+        println!("watching for changes...");
+
+        pending = Vec::new();
+        while pending.is_empty() {
+            thread::sleep(POLL);
+            if let (Some(exe), Some(binary)) = (exe, binary.as_mut()) {
+                if !binary.changed().is_empty() {
+                    wait_until_written(exe);
+                    let error = restart(exe, &answers);
+                    return Err(AocError::Io {
+                        path: exe.display().to_string(),
+                        source: error,
+                    });
+                }
+            }
+            for (&puzzle, stamps) in puzzles.iter().zip(stamps.iter_mut()) {
+                let changed = stamps.changed();
+                for path in &changed {
+                    println!("{} changed", path.display());
+                }
+                if !changed.is_empty() {
+                    pending.push(puzzle);
+                }
+            }
+        }
+    }
+}
+
+fn print_changes(outcome: &DayOutcome, answers: &mut Answers) {
+    let elapsed = outcome.records.iter().map(|r| r.elapsed).sum::<Duration>();
+    println!(
+        "day {} ({:.3}ms)",
+        outcome.day,
+        elapsed.as_secs_f64() * 1000.0
+    );
+    for record in &outcome.records {
+        let previous = answers.insert(outcome.day, record.part, record.answer.clone());
+        println!("{}", describe(record, previous.as_ref()));
+    }
+    if let Some(error) = &outcome.error {
+        eprintln!("Error: {}", error);
+    }
+}
+
+// the answers a binary that restarted itself was given, if this is one
+fn previous_answers() -> Answers {
+    env::var(PREVIOUS_ENV)
+        .ok()
+        .and_then(|text| Answers::parse(text.lines().map(|l| l.to_string()).collect()).ok())
+        .unwrap_or_default()
+}
+
+// the binary is written in several steps, so we wait until it stops changing before running it
+fn wait_until_written(exe: &Path) {
+    let mut stamps = Stamps::new(vec![exe.to_path_buf()]);
+    loop {
+        thread::sleep(POLL);
+        if stamps.changed().is_empty() && modified(exe).is_some() {
+            return;
+        }
+    }
+}
+
+// Replaces this process with the binary at `exe`, run with the same arguments. Returns what went wrong
+// when it can't
+fn restart(exe: &Path, answers: &Answers) -> io::Error {
+    println!("{} changed, restarting", exe.display());
+    let mut command = Command::new(exe);
+    command
+        .args(env::args_os().skip(1))
+        .env(PREVIOUS_ENV, answers.to_text());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.exec()
+    }
+    #[cfg(not(unix))]
+    {
+        match command.status() {
+            Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+            Err(error) => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(answer: &str) -> Record {
+        Record {
+            year: 2021,
+            day: 1,
+            part: Part::One,
+            answer: answer.to_string(),
+            sample_passed: true,
+            elapsed: Duration::ZERO,
+            parse_memory: None,
+            memory: None,
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn notices_changed_files() {
+        let path = env::temp_dir().join(format!(
+            "aoc-watch-{}-notices_changed_files.txt",
+            std::process::id()
+        ));
+        fs::write(&path, "1").unwrap();
+        let mut stamps = Stamps::new(vec![path.clone()]);
+        assert!(stamps.changed().is_empty());
+
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(stamps.changed(), vec![path.clone()]);
+        assert!(stamps.changed().is_empty());
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(stamps.changed(), vec![path]);
+    }
+
+    #[test]
+    fn describes_answer_changes() {
+        let previous = "1521".to_string();
+        assert_eq!(describe(&record("1521"), None), "part 1: 1521");
+        assert_eq!(
+            describe(&record("1521"), Some(&previous)),
+            "part 1: 1521 (unchanged)"
+        );
+        assert_eq!(
+            describe(&record("1600"), Some(&previous)),
+            "part 1: 1600 (was 1521)"
+        );
+        assert_eq!(
+            describe(&record("#.\n.#"), Some(&previous)),
+            "part 1: changed, was:\n1521\nnow:\n#.\n.#"
+        );
+    }
+}